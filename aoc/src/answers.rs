@@ -0,0 +1,227 @@
+//! Answers accepted in earlier runs, so refactors can be checked against
+//! them.
+//!
+//! The file has one answer per line: year, day, part, the input's
+//! [`fingerprint`] and the answer, separated by spaces. Blank lines and lines
+//! starting with `#` are ignored.
+
+use common::Answer;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Where answers are kept unless `--answers` says otherwise.
+pub const DEFAULT_PATH: &str = "answers.txt";
+
+/// A hash of the puzzle input, so answers for different inputs (yours and a
+/// friend's, or an example) are kept apart.
+///
+/// This is 64 bit FNV-1a: stable across builds and platforms, which
+/// `std`'s hasher doesn't promise.
+pub fn fingerprint(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub fingerprint: u64,
+}
+
+/// How an answer compares to the one on record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong { expected: String },
+    New,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Correct => write!(f, "correct"),
+            Status::Wrong { expected } => write!(f, "wrong, expected {expected}"),
+            Status::New => write!(f, "new"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// A line that isn't `<year> <day> <part> <fingerprint> <answer>`.
+    Malformed {
+        path: PathBuf,
+        line: usize,
+    },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "Failed to access {}: {source}", path.display())
+            }
+            AnswersError::Malformed { path, line } => write!(
+                f,
+                "{}:{line}: expected `<year> <day> <part> <fingerprint> <answer>`",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: BTreeMap<Key, String>,
+}
+
+impl Answers {
+    /// Read the answers at `path`. A missing file has no answers yet.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|line| AnswersError::Malformed {
+                path: path.to_path_buf(),
+                line,
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(AnswersError::Io {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        fs::write(path, self.to_string()).map_err(|source| AnswersError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Parse the file format, or return the number of the first bad line.
+    fn parse(text: &str) -> Result<Self, usize> {
+        let mut entries = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(5, ' ');
+            let mut field = || fields.next().ok_or(i + 1);
+            let key = Key {
+                year: field()?.parse().map_err(|_| i + 1)?,
+                day: field()?.parse().map_err(|_| i + 1)?,
+                part: field()?.parse().map_err(|_| i + 1)?,
+                fingerprint: u64::from_str_radix(field()?, 16).map_err(|_| i + 1)?,
+            };
+            entries.insert(key, field()?.to_string());
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn check(&self, key: &Key, answer: &Answer) -> Status {
+        match self.entries.get(key) {
+            None => Status::New,
+            Some(expected) if *expected == answer.to_string() => Status::Correct,
+            Some(expected) => Status::Wrong {
+                expected: expected.clone(),
+            },
+        }
+    }
+
+    /// Accept `answer`, replacing any answer already on record.
+    pub fn record(&mut self, key: Key, answer: &Answer) {
+        self.entries.insert(key, answer.to_string());
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# year day part input-fingerprint answer")?;
+        for (key, answer) in &self.entries {
+            writeln!(
+                f,
+                "{} {:02} {} {:016x} {answer}",
+                key.year, key.day, key.part, key.fingerprint
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(part: u8) -> Key {
+        Key {
+            year: 2023,
+            day: 6,
+            part,
+            fingerprint: fingerprint("Time: 7\nDistance: 9"),
+        }
+    }
+
+    #[test]
+    fn fingerprints() {
+        assert_eq!(fingerprint(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(fingerprint("1 2"), fingerprint("2 1"));
+    }
+
+    #[test]
+    fn check() {
+        let mut answers = Answers::default();
+        assert_eq!(answers.check(&key(1), &Answer::from(4u64)), Status::New);
+
+        answers.record(key(1), &Answer::from(4u64));
+        assert_eq!(answers.check(&key(1), &Answer::from(4u64)), Status::Correct);
+        assert_eq!(
+            answers.check(&key(1), &Answer::from(5u64)),
+            Status::Wrong {
+                expected: "4".to_string()
+            }
+        );
+        assert_eq!(answers.check(&key(2), &Answer::from(4u64)), Status::New);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut answers = Answers::default();
+        answers.record(key(2), &Answer::from(-71503i64));
+        answers.record(key(1), &Answer::from("SOME TEXT"));
+
+        let text = answers.to_string();
+        assert_eq!(
+            text,
+            "# year day part input-fingerprint answer
+2023 06 1 3e5c94ec67ed0efd SOME TEXT
+2023 06 2 3e5c94ec67ed0efd -71503
+"
+        );
+
+        let parsed = Answers::parse(&text).unwrap();
+        assert_eq!(parsed.entries, answers.entries);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(Answers::parse("\n# ok\n2023 6 1 ff").unwrap_err(), 3);
+        assert_eq!(Answers::parse("2023 6 1 xyz 4").unwrap_err(), 1);
+    }
+}
@@ -0,0 +1,266 @@
+//! Timing each day's stages over many runs, and comparing the timings with
+//! an earlier baseline.
+
+use crate::{answers, days::Day};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Flag a stage whose median grew by more than this many percent.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part1 => write!(f, "part 1"),
+            Stage::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+/// Summary of a stage's timings, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn new(times: &[Duration]) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = times.iter().map(|t| t.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+
+        let runs = nanos.len();
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let median = match runs % 2 {
+            0 => (nanos[runs / 2 - 1] + nanos[runs / 2]) / 2.0,
+            _ => nanos[runs / 2],
+        };
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+
+        Some(Self {
+            runs,
+            mean,
+            median,
+            stddev: variance.sqrt(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    /// [`answers::fingerprint`] of the input, as hex. Timings for
+    /// different inputs aren't compared.
+    pub input: String,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub year: u16,
+    pub measurements: Vec<Measurement>,
+}
+
+#[derive(Debug)]
+pub enum BenchError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+impl Display for BenchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchError::Io { path, source } => {
+                write!(f, "Failed to access {}: {source}", path.display())
+            }
+            BenchError::Json { path, source } => {
+                write!(f, "{} is not a benchmark report: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for BenchError {}
+
+impl Report {
+    pub fn load(path: &Path) -> Result<Self, BenchError> {
+        let text = fs::read_to_string(path).map_err(|source| BenchError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_str(&text).map_err(|source| BenchError::Json {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchError> {
+        let json = serde_json::to_string_pretty(self).map_err(|source| BenchError::Json {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        fs::write(path, json + "\n").map_err(|source| BenchError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// The measurement of the same day, stage and input in `self`.
+    fn find(&self, m: &Measurement) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|b| b.day == m.day && b.stage == m.stage && b.input == m.input)
+    }
+}
+
+/// Time every stage of `day` on `input`, `runs` times over.
+///
+/// Returns `None` if the input doesn't parse, since there's nothing to time.
+pub fn measure(day: &Day, input: &str, runs: usize) -> Option<Vec<Measurement>> {
+    // Warm up, and make sure there's something to time.
+    (day.execute)(input, &[1, 2]).ok()?;
+
+    let mut times: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..runs {
+        let execution = (day.execute)(input, &[1, 2]).ok()?;
+        times[0].push(execution.parse_time);
+        for part in execution.parts {
+            times[usize::from(part.part)].push(part.time);
+        }
+    }
+
+    let fingerprint = format!("{:016x}", answers::fingerprint(input));
+    let measurements = [Stage::Parse, Stage::Part1, Stage::Part2]
+        .into_iter()
+        .zip(times)
+        .filter_map(|(stage, times)| {
+            Some(Measurement {
+                day: day.day,
+                stage,
+                input: fingerprint.clone(),
+                stats: Stats::new(&times)?,
+            })
+        })
+        .collect();
+
+    Some(measurements)
+}
+
+/// How much slower `m` is than its baseline, in percent of the baseline's
+/// median. `None` if there's no baseline for it.
+pub fn change(baseline: &Report, m: &Measurement) -> Option<f64> {
+    let before = baseline.find(m)?.stats.median;
+    (before > 0.0).then(|| (m.stats.median - before) / before * 100.0)
+}
+
+/// A duration in nanoseconds, in the unit that suits it.
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.2?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(stage: Stage, median: f64) -> Measurement {
+        Measurement {
+            day: 6,
+            stage,
+            input: "00000000000000ff".to_string(),
+            stats: Stats {
+                runs: 3,
+                mean: median,
+                median,
+                stddev: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let times: Vec<Duration> = [4, 1, 3, 2].map(Duration::from_nanos).to_vec();
+        let stats = Stats::new(&times).unwrap();
+        assert_eq!((stats.runs, stats.mean, stats.median), (4, 2.5, 2.5));
+        assert_eq!(stats.stddev, 1.25f64.sqrt());
+
+        let stats = Stats::new(&times[..3]).unwrap();
+        assert_eq!(stats.median, 3.0);
+
+        assert_eq!(Stats::new(&[]), None);
+    }
+
+    #[test]
+    fn compare() {
+        let baseline = Report {
+            year: 2023,
+            measurements: vec![measurement(Stage::Part1, 200.0)],
+        };
+
+        assert_eq!(
+            change(&baseline, &measurement(Stage::Part1, 250.0)),
+            Some(25.0)
+        );
+        assert_eq!(
+            change(&baseline, &measurement(Stage::Part1, 100.0)),
+            Some(-50.0)
+        );
+        assert_eq!(change(&baseline, &measurement(Stage::Part2, 100.0)), None);
+
+        let mut other_input = measurement(Stage::Part1, 250.0);
+        other_input.input = "0000000000000001".to_string();
+        assert_eq!(change(&baseline, &other_input), None);
+    }
+
+    #[test]
+    fn json() {
+        let report = Report {
+            year: 2023,
+            measurements: vec![measurement(Stage::Parse, 12.0)],
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            json,
+            r#"{"year":2023,"measurements":[{"day":6,"stage":"parse","input":"00000000000000ff","runs":3,"mean":12.0,"median":12.0,"stddev":0.0}]}"#
+        );
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn measures_every_stage() {
+        let day = crate::days::find(6).unwrap();
+        let measurements = measure(day, "Time: 7\nDistance: 9", 5).unwrap();
+        let stages: Vec<Stage> = measurements.iter().map(|m| m.stage).collect();
+        assert_eq!(stages, vec![Stage::Parse, Stage::Part1, Stage::Part2]);
+        assert!(measurements.iter().all(|m| m.stats.runs == 5));
+
+        assert_eq!(measure(day, "nonsense", 5), None);
+    }
+}
@@ -0,0 +1,39 @@
+//! Every day's solutions, from the day crates.
+
+use crate::execute::{execute, Execution};
+use common::ParseError;
+
+macro_rules! days {
+    ($($n:literal => $krate:ident::$solution:ident),* $(,)?) => {
+        pub const DAYS: &[Day] = &[
+            $(Day {
+                day: $n,
+                execute: execute::<$krate::$solution>,
+            },)*
+        ];
+    };
+}
+
+#[derive(Debug)]
+pub struct Day {
+    pub day: u8,
+    pub execute: fn(&str, &[u8]) -> Result<Execution, ParseError>,
+}
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+days! {
+    1 => day_1::Day1,
+    2 => day_2::Day2,
+    3 => day_3::Day3,
+    4 => day_4::Day4,
+    5 => day_5::Day5,
+    6 => day_6::Day6,
+    7 => day_7::Day7,
+    8 => day_8::Day8,
+    9 => day_9::Day9,
+    10 => day_10::Day10,
+    11 => day_11::Day11,
+}
@@ -0,0 +1,44 @@
+//! Runs a day's stages and times each of them.
+
+use common::{Answer, ParseError, Solution, SolveError};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<Answer, SolveError>,
+    pub time: Duration,
+}
+
+#[derive(Debug)]
+pub struct Execution {
+    pub parse_time: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+/// Parse `input` once and solve each of `parts` from the same model.
+pub fn execute<S: Solution>(input: &str, parts: &[u8]) -> Result<Execution, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part_1(&parsed),
+                2 => S::part_2(&parsed),
+                _ => unreachable!("parts are validated by the cli"),
+            };
+
+            PartResult {
+                part,
+                answer,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Execution { parse_time, parts })
+}
@@ -0,0 +1,527 @@
+use answers::{Answers, Key, Status};
+use bench::Report;
+use clap::{Parser, Subcommand};
+use common::{input, trace, Answer};
+use output::{Format, Outcome, Printer, Row};
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+mod answers;
+mod bench;
+mod days;
+mod execute;
+mod output;
+mod scaffold;
+mod site;
+mod submit;
+
+use days::Day;
+
+#[derive(Debug, Parser)]
+#[command(about = "Runs the Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Show a day's diagnostics on stderr, e.g. `day10` or `day8=debug`.
+    /// Repeat it or separate days with commas; `all` shows every day.
+    /// Defaults to `$AOC_TRACE`
+    #[arg(long, global = true, value_name = "DAY[=LEVEL]")]
+    trace: Vec<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the given days, or `all` of them
+    Run {
+        #[arg(required = true, value_name = "DAY|all")]
+        days: Vec<String>,
+
+        /// Only run this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Read the input from this file, or `-` for stdin. Only valid when
+        /// running a single day; otherwise `$AOC_INPUT_DIR/day-NN.txt` and
+        /// `inputs/day-NN.txt` are searched
+        #[arg(long, short)]
+        input: Option<String>,
+
+        /// Compare answers against the ones recorded in this file
+        #[arg(long, value_name = "PATH", default_value = answers::DEFAULT_PATH)]
+        answers: PathBuf,
+
+        /// Record answers that aren't in the answers file yet
+        #[arg(long)]
+        accept: bool,
+
+        /// Exit with an error if any answer is wrong or any part fails
+        #[arg(long)]
+        check: bool,
+
+        /// How to print the results
+        #[arg(long, value_enum, default_value_t = Format::Human)]
+        format: Format,
+    },
+    /// Time each stage of the given days, or `all` of them, over many runs
+    Bench {
+        #[arg(required = true, value_name = "DAY|all")]
+        days: Vec<String>,
+
+        /// How many times to run each day
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+        runs: u64,
+
+        /// Write the timings to this JSON file
+        #[arg(long, value_name = "PATH")]
+        save: Option<PathBuf>,
+
+        /// Compare with timings saved earlier with `--save`, and exit with an
+        /// error if any stage got slower
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<PathBuf>,
+
+        /// How many percent slower than the baseline's median counts as a
+        /// regression
+        #[arg(long, default_value_t = bench::DEFAULT_THRESHOLD)]
+        threshold: f64,
+    },
+    /// Create a crate for a new day from the template and register it.
+    /// Run this from the workspace root
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Download a day's input into `$AOC_INPUT_DIR` or `inputs`, unless it's
+    /// already there. The session token comes from `$AOC_SESSION` or
+    /// `~/.config/aoc/session`
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Send a day's answer to the site and log the verdict. Answers the log
+    /// already rules out aren't sent
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Send this instead of running the day for the answer
+        #[arg(long)]
+        answer: Option<String>,
+
+        /// Read the input from this file, or `-` for stdin
+        #[arg(long, short)]
+        input: Option<String>,
+
+        /// Where submissions and their verdicts are logged
+        #[arg(long, value_name = "PATH", default_value = submit::DEFAULT_PATH)]
+        log: PathBuf,
+
+        /// Record a correct answer in this file, for `run` to check against
+        #[arg(long, value_name = "PATH", default_value = answers::DEFAULT_PATH)]
+        answers: PathBuf,
+    },
+}
+
+/// How the answers file is used during a run.
+struct Record<'a> {
+    path: &'a Path,
+    answers: Answers,
+    accept: bool,
+}
+
+fn select(args: &[String]) -> Result<Vec<&'static Day>, String> {
+    if args.iter().any(|a| a == "all") {
+        return Ok(days::DAYS.iter().collect());
+    }
+
+    let mut selected = Vec::new();
+    for arg in args {
+        let day: u8 = arg
+            .parse()
+            .map_err(|_| format!("\"{arg}\" is not a day number"))?;
+        let day = days::find(day).ok_or_else(|| format!("day {arg} is not solved yet"))?;
+        if !selected.iter().any(|d: &&Day| d.day == day.day) {
+            selected.push(day);
+        }
+    }
+
+    Ok(selected)
+}
+
+/// Run `days`, returning whether every part gave the recorded answer (or one
+/// that's new).
+fn run<W: io::Write>(
+    days: &[&Day],
+    part: Option<u8>,
+    explicit: Option<&str>,
+    record: &mut Record,
+    printer: &mut Printer<W>,
+) -> io::Result<bool> {
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let failed = |day: &Day, message: String, detail: Option<String>| Row {
+        day: day.day,
+        part: None,
+        answer: None,
+        time: None,
+        outcome: Outcome::Failed { message, detail },
+    };
+
+    let mut ok = true;
+    for day in days {
+        let text = match input::read(day.day, days.len() == 1, explicit) {
+            Ok(text) => text,
+            Err(e) => {
+                printer.row(&failed(day, e.to_string(), None))?;
+                ok = false;
+                continue;
+            }
+        };
+
+        let execution = match (day.execute)(&text, &parts) {
+            Ok(execution) => execution,
+            Err(e) => {
+                printer.row(&failed(day, e.to_string(), Some(e.render(&text))))?;
+                ok = false;
+                continue;
+            }
+        };
+        printer.day(day.day, execution.parse_time)?;
+        let fingerprint = answers::fingerprint(&text);
+        for result in execution.parts {
+            let (answer, outcome) = match result.answer {
+                Ok(answer) => {
+                    let key = Key {
+                        year: common::YEAR,
+                        day: day.day,
+                        part: result.part,
+                        fingerprint,
+                    };
+                    let status = record.answers.check(&key, &answer);
+                    match status {
+                        Status::Wrong { .. } => ok = false,
+                        Status::New if record.accept => record.answers.record(key, &answer),
+                        _ => {}
+                    }
+                    (Some(answer.to_string()), Outcome::Checked(status))
+                }
+                Err(e) => {
+                    ok = false;
+                    let message = e.to_string();
+                    (
+                        None,
+                        Outcome::Failed {
+                            message,
+                            detail: None,
+                        },
+                    )
+                }
+            };
+
+            printer.row(&Row {
+                day: day.day,
+                part: Some(result.part),
+                answer,
+                time: Some(result.time),
+                outcome,
+            })?;
+        }
+    }
+
+    if record.accept {
+        if let Err(e) = record.answers.save(record.path) {
+            eprintln!("error: {e}");
+            ok = false;
+        }
+    }
+
+    Ok(ok)
+}
+
+/// Run `day` for the answer to `part`, with the key it's recorded under.
+fn solve(day: u8, part: u8, explicit: Option<&str>) -> Result<(Answer, Key), String> {
+    let solution = days::find(day).ok_or_else(|| format!("day {day} is not solved yet"))?;
+    let text = input::read(day, true, explicit).map_err(|e| e.to_string())?;
+    let execution = (solution.execute)(&text, &[part]).map_err(|e| e.render(&text))?;
+
+    let answer = execution
+        .parts
+        .into_iter()
+        .next()
+        .expect("one part was asked for")
+        .answer
+        .map_err(|e| e.to_string())?;
+    let key = Key {
+        year: common::YEAR,
+        day,
+        part,
+        fingerprint: answers::fingerprint(&text),
+    };
+
+    Ok((answer, key))
+}
+
+/// Benchmark `days`, returning the timings and whether any stage regressed
+/// against `baseline`.
+fn bench(days: &[&Day], runs: usize, baseline: Option<&Report>, threshold: f64) -> (Report, bool) {
+    let mut report = Report {
+        year: common::YEAR,
+        measurements: Vec::new(),
+    };
+    let mut regressed = false;
+
+    for day in days {
+        let text = match input::read(day.day, days.len() == 1, None) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("Day {}: {e}", day.day);
+                continue;
+            }
+        };
+        let Some(measurements) = bench::measure(day, &text, runs) else {
+            eprintln!(
+                "Day {}: the input doesn't parse, run it for details",
+                day.day
+            );
+            continue;
+        };
+
+        for m in measurements {
+            let mut line = format!(
+                "Day {:>2} {:<6}  mean {:>10}  median {:>10}  stddev {:>10}",
+                m.day,
+                m.stage,
+                bench::format_nanos(m.stats.mean),
+                bench::format_nanos(m.stats.median),
+                bench::format_nanos(m.stats.stddev),
+            );
+            if let Some(change) = baseline.and_then(|b| bench::change(b, &m)) {
+                line += &format!("  {change:+.1}%");
+                if change > threshold {
+                    line += " REGRESSION";
+                    regressed = true;
+                }
+            }
+            println!("{line}");
+            report.measurements.push(m);
+        }
+    }
+
+    (report, regressed)
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if cli.trace.is_empty() {
+        trace::from_env();
+    } else {
+        match trace::filters(&cli.trace.join(",")) {
+            Ok(filters) => trace::init(filters),
+            Err(e) => {
+                eprintln!("error: {e}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    match cli.command {
+        Command::Run {
+            days,
+            part,
+            input,
+            answers,
+            accept,
+            check,
+            format,
+        } => {
+            let days = match select(&days) {
+                Ok(days) if input.is_some() && days.len() > 1 => {
+                    eprintln!("error: --input can only be used with a single day");
+                    std::process::exit(2);
+                }
+                Ok(days) => days,
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    std::process::exit(2);
+                }
+            };
+            let mut record = match Answers::load(&answers) {
+                Ok(loaded) => Record {
+                    path: &answers,
+                    answers: loaded,
+                    accept,
+                },
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    std::process::exit(2);
+                }
+            };
+
+            let ok = Printer::new(format, io::stdout().lock()).and_then(|mut printer| {
+                let ok = run(&days, part, input.as_deref(), &mut record, &mut printer)?;
+                printer.finish()?;
+                Ok(ok)
+            });
+            match ok {
+                Ok(false) if check => std::process::exit(1),
+                Ok(_) => {}
+                // Whoever was reading stopped, e.g. `head`
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Bench {
+            days,
+            runs,
+            save,
+            baseline,
+            threshold,
+        } => {
+            let days = select(&days).unwrap_or_else(|e| {
+                eprintln!("error: {e}");
+                std::process::exit(2);
+            });
+            let baseline = baseline.map(|path| {
+                Report::load(&path).unwrap_or_else(|e| {
+                    eprintln!("error: {e}");
+                    std::process::exit(2);
+                })
+            });
+
+            let (report, regressed) = bench(&days, runs as usize, baseline.as_ref(), threshold);
+            if let Some(path) = save {
+                if let Err(e) = report.save(&path) {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+            if regressed {
+                std::process::exit(1);
+            }
+        }
+        Command::New { day } => match scaffold::scaffold(Path::new("."), day) {
+            Ok(written) => {
+                for path in written {
+                    println!("wrote {}", path.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        },
+        Command::Submit {
+            day,
+            part,
+            answer,
+            input,
+            log,
+            answers,
+        } => {
+            let fail = |e: &dyn Display| -> ! {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            };
+
+            let (answer, key) = match answer {
+                Some(answer) => (Answer::from(answer.as_str()), None),
+                None => match solve(day, part, input.as_deref()) {
+                    Ok((answer, key)) => (answer, Some(key)),
+                    Err(e) => fail(&e),
+                },
+            };
+            let text = answer.to_string();
+            println!("Day {day} part {part}: {text}");
+
+            let mut submissions = submit::Log::load(&log).unwrap_or_else(|e| fail(&e));
+            let puzzle = submit::Puzzle {
+                year: common::YEAR,
+                day,
+                part,
+            };
+            let verdict = submit::submit(puzzle, &text, &mut submissions, site::Client::from_env)
+                .unwrap_or_else(|e| fail(&e));
+            submissions.save(&log).unwrap_or_else(|e| fail(&e));
+            println!("{verdict}");
+
+            if verdict != submit::Verdict::Correct {
+                std::process::exit(1);
+            }
+            if let Some(key) = key {
+                let recorded = Answers::load(&answers).and_then(|mut recorded| {
+                    recorded.record(key, &answer);
+                    recorded.save(&answers)
+                });
+                recorded.unwrap_or_else(|e| fail(&e));
+            }
+        }
+        Command::Fetch { day } => {
+            match site::fetch(day, &site::input_dir(), site::Client::from_env) {
+                Ok(site::Fetched::Cached(path)) => println!("{} is already there", path.display()),
+                Ok(site::Fetched::Downloaded(path)) => println!("wrote {}", path.display()),
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{Answer, SolveError};
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn select_all() {
+        assert_eq!(select(&args(&["all"])).unwrap().len(), days::DAYS.len());
+    }
+
+    #[test]
+    fn select_subset() {
+        let days: Vec<u8> = select(&args(&["5", "2", "5"]))
+            .unwrap()
+            .iter()
+            .map(|d| d.day)
+            .collect();
+        assert_eq!(days, vec![5, 2]);
+
+        assert!(select(&args(&["five"])).is_err());
+        assert!(select(&args(&["25"])).is_err());
+    }
+
+    #[test]
+    fn dispatch() {
+        let day = days::find(6).unwrap();
+        let input = "Time:      7  15   30
+Distance:  9  40  200";
+
+        let answers: Vec<Result<Answer, SolveError>> = (day.execute)(input, &[2, 1])
+            .unwrap()
+            .parts
+            .into_iter()
+            .map(|r| r.answer)
+            .collect();
+        assert_eq!(
+            answers,
+            vec![Ok(Answer::Unsigned(71503)), Ok(Answer::Unsigned(288))]
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Printing results as a table for people, or as JSON lines, CSV or TAP for
+//! scripts.
+
+use crate::answers::Status;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{io, io::Write, time::Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// A line per part under a heading per day
+    Human,
+    /// A JSON object per line
+    Json,
+    /// Comma separated values with a header line
+    Csv,
+    /// The Test Anything Protocol, a test point per part
+    Tap,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// An answer, compared with the one on record.
+    Checked(Status),
+    /// No answer: the input was missing or didn't parse, or the part failed.
+    Failed {
+        message: String,
+        /// A longer explanation for people, such as a rendered diagnostic.
+        detail: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub day: u8,
+    /// `None` when the whole day failed.
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub time: Option<Duration>,
+    pub outcome: Outcome,
+}
+
+impl Row {
+    fn status(&self) -> &'static str {
+        match &self.outcome {
+            Outcome::Checked(Status::Correct) => "correct",
+            Outcome::Checked(Status::Wrong { .. }) => "wrong",
+            Outcome::Checked(Status::New) => "new",
+            Outcome::Failed { .. } => "error",
+        }
+    }
+
+    /// The expected answer, or what went wrong.
+    fn detail(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Checked(Status::Wrong { expected }) => Some(expected),
+            Outcome::Checked(_) => None,
+            Outcome::Failed { message, .. } => Some(message),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRow<'a> {
+    day: u8,
+    part: Option<u8>,
+    answer: Option<&'a str>,
+    time_ns: Option<u128>,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// Quote a CSV field if it needs it.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Writes rows to `out` as they come in, in one [`Format`].
+pub struct Printer<W: Write> {
+    format: Format,
+    out: W,
+    /// Rows written so far, for TAP's test numbers and plan.
+    rows: usize,
+}
+
+impl<W: Write> Printer<W> {
+    pub fn new(format: Format, mut out: W) -> io::Result<Self> {
+        match format {
+            Format::Csv => writeln!(out, "day,part,answer,time_ns,status,detail")?,
+            Format::Tap => writeln!(out, "TAP version 13")?,
+            Format::Human | Format::Json => {}
+        }
+
+        Ok(Self {
+            format,
+            out,
+            rows: 0,
+        })
+    }
+
+    /// Start `day`'s results, once its input has parsed.
+    pub fn day(&mut self, day: u8, parse_time: Duration) -> io::Result<()> {
+        match self.format {
+            Format::Human => writeln!(self.out, "Day {day} (parsed in {parse_time:?})"),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn row(&mut self, row: &Row) -> io::Result<()> {
+        self.rows += 1;
+        match self.format {
+            Format::Human => self.human(row),
+            Format::Json => {
+                let time_ns = row.time.map(|t| t.as_nanos());
+                let (expected, error) = match &row.outcome {
+                    Outcome::Checked(_) => (row.detail(), None),
+                    Outcome::Failed { message, .. } => (None, Some(message.as_str())),
+                };
+                let json = JsonRow {
+                    day: row.day,
+                    part: row.part,
+                    answer: row.answer.as_deref(),
+                    time_ns,
+                    status: row.status(),
+                    expected,
+                    error,
+                };
+                serde_json::to_writer(&mut self.out, &json)?;
+                writeln!(self.out)
+            }
+            Format::Csv => writeln!(
+                self.out,
+                "{},{},{},{},{},{}",
+                row.day,
+                row.part.map(|p| p.to_string()).unwrap_or_default(),
+                csv_field(row.answer.as_deref().unwrap_or_default()),
+                row.time
+                    .map(|t| t.as_nanos().to_string())
+                    .unwrap_or_default(),
+                row.status(),
+                csv_field(row.detail().unwrap_or_default()),
+            ),
+            Format::Tap => self.tap(row),
+        }
+    }
+
+    fn human(&mut self, row: &Row) -> io::Result<()> {
+        let time = row.time.map(|t| format!(" ({t:?})")).unwrap_or_default();
+        match (&row.outcome, row.part) {
+            (Outcome::Checked(status), Some(part)) => {
+                let answer = row.answer.as_deref().unwrap_or_default();
+                writeln!(self.out, "  part {part}: {answer}{time} [{status}]")
+            }
+            (Outcome::Failed { message, .. }, Some(part)) => {
+                writeln!(self.out, "  part {part}: {message}{time}")
+            }
+            (Outcome::Failed { message, detail }, None) => match detail {
+                Some(detail) => writeln!(self.out, "{detail}"),
+                None => writeln!(self.out, "Day {}: {message}", row.day),
+            },
+            (Outcome::Checked(_), None) => Ok(()),
+        }
+    }
+
+    fn tap(&mut self, row: &Row) -> io::Result<()> {
+        let ok = match row.outcome {
+            Outcome::Checked(Status::Correct | Status::New) => "ok",
+            _ => "not ok",
+        };
+        let mut line = format!("{ok} {} - day {}", self.rows, row.day);
+        if let Some(part) = row.part {
+            line += &format!(" part {part}");
+        }
+        match (&row.outcome, &row.answer) {
+            (Outcome::Checked(status), Some(answer)) => line += &format!(": {answer} [{status}]"),
+            (Outcome::Failed { message, .. }, _) => {
+                line += &format!(": {}", message.lines().next().unwrap_or_default())
+            }
+            _ => {}
+        }
+        writeln!(self.out, "{line}")?;
+
+        if let Some(time) = row.time {
+            writeln!(self.out, "# took {time:?}")?;
+        }
+        if let Outcome::Failed { message, detail } = &row.outcome {
+            let rest = message.lines().skip(1);
+            for line in rest.chain(detail.iter().flat_map(|d| d.lines())) {
+                writeln!(self.out, "# {line}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write anything that has to come after every row.
+    pub fn finish(mut self) -> io::Result<()> {
+        if self.format == Format::Tap {
+            writeln!(self.out, "1..{}", self.rows)?;
+        }
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                day: 6,
+                part: Some(1),
+                answer: Some("288".to_string()),
+                time: Some(Duration::from_nanos(1500)),
+                outcome: Outcome::Checked(Status::Correct),
+            },
+            Row {
+                day: 6,
+                part: Some(2),
+                answer: Some("71503".to_string()),
+                time: Some(Duration::from_nanos(300)),
+                outcome: Outcome::Checked(Status::Wrong {
+                    expected: "71504".to_string(),
+                }),
+            },
+            Row {
+                day: 10,
+                part: None,
+                answer: None,
+                time: None,
+                outcome: Outcome::Failed {
+                    message: "day 10, line 1, column 2: expected a pipe, found \"X\"".to_string(),
+                    detail: Some("error: expected a pipe\n --> day 10".to_string()),
+                },
+            },
+        ]
+    }
+
+    fn print(format: Format) -> String {
+        let mut out = Vec::new();
+        let mut printer = Printer::new(format, &mut out).unwrap();
+        printer.day(6, Duration::from_micros(12)).unwrap();
+        for row in rows() {
+            printer.row(&row).unwrap();
+        }
+        printer.finish().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn human() {
+        assert_eq!(
+            print(Format::Human),
+            "Day 6 (parsed in 12µs)
+  part 1: 288 (1.5µs) [correct]
+  part 2: 71503 (300ns) [wrong, expected 71504]
+error: expected a pipe
+ --> day 10
+"
+        );
+    }
+
+    #[test]
+    fn human_failures() {
+        let mut out = Vec::new();
+        let mut printer = Printer::new(Format::Human, &mut out).unwrap();
+        printer.day(7, Duration::from_micros(3)).unwrap();
+        let failed = |part, message: &str, detail: Option<&str>| Row {
+            day: 7,
+            part,
+            answer: None,
+            time: part.map(|_| Duration::from_nanos(40)),
+            outcome: Outcome::Failed {
+                message: message.to_string(),
+                detail: detail.map(str::to_string),
+            },
+        };
+        printer
+            .row(&failed(Some(2), "overflow: total winnings", None))
+            .unwrap();
+        printer.row(&failed(None, "no input", None)).unwrap();
+        printer
+            .row(&failed(None, "bad hand", Some("error: expected a card")))
+            .unwrap();
+        printer.finish().unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Day 7 (parsed in 3µs)
+  part 2: overflow: total winnings (40ns)
+Day 7: no input
+error: expected a card
+"
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            print(Format::Json),
+            r#"{"day":6,"part":1,"answer":"288","time_ns":1500,"status":"correct"}
+{"day":6,"part":2,"answer":"71503","time_ns":300,"status":"wrong","expected":"71504"}
+{"day":10,"part":null,"answer":null,"time_ns":null,"status":"error","error":"day 10, line 1, column 2: expected a pipe, found \"X\""}
+"#
+        );
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            print(Format::Csv),
+            r#"day,part,answer,time_ns,status,detail
+6,1,288,1500,correct,
+6,2,71503,300,wrong,71504
+10,,,,error,"day 10, line 1, column 2: expected a pipe, found ""X"""
+"#
+        );
+    }
+
+    #[test]
+    fn tap() {
+        assert_eq!(
+            print(Format::Tap),
+            r#"TAP version 13
+ok 1 - day 6 part 1: 288 [correct]
+# took 1.5µs
+not ok 2 - day 6 part 2: 71503 [wrong, expected 71504]
+# took 300ns
+not ok 3 - day 10: day 10, line 1, column 2: expected a pipe, found "X"
+# error: expected a pipe
+#  --> day 10
+1..3
+"#
+        );
+    }
+}
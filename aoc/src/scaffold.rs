@@ -0,0 +1,283 @@
+//! Creating a new day's crate from the template and registering it with
+//! the runner.
+
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The day's library; its binary only calls [`common::run`].
+const TEMPLATE: &str = include_str!("../template.rs");
+
+/// The runner's registry of days, relative to the workspace root.
+const REGISTRY: &str = "aoc/src/days.rs";
+
+/// The runner's manifest, which depends on every day crate.
+const MANIFEST: &str = "aoc/Cargo.toml";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day's directory is already there.
+    Exists(PathBuf),
+    /// The runner already lists the day, or has nowhere to list it.
+    Registry {
+        path: PathBuf,
+        why: String,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => {
+                write!(f, "{} already exists, not overwriting it", path.display())
+            }
+            ScaffoldError::Registry { path, why } => write!(f, "{}: {why}", path.display()),
+            ScaffoldError::Io { path, source } => {
+                write!(f, "Failed to access {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// The template filled in for `day`.
+fn library(day: u8) -> String {
+    let source = TEMPLATE
+        .replace("DayN", &format!("Day{day}"))
+        .replace("u8 = N;", &format!("u8 = {day};"));
+    format!("//! Day {day}\n\n{source}")
+}
+
+fn binary(day: u8) -> String {
+    format!("fn main() {{\n    common::run::<day_{day}::Day{day}>();\n}}\n")
+}
+
+fn manifest(day: u8) -> String {
+    format!(
+        r#"[package]
+name = "day-{day}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+common = {{ path = "../common" }}
+"#
+    )
+}
+
+/// Insert the line `entry` among the lines that `number` recognises as
+/// belonging to a day, keeping them in order.
+fn insert_in_order(
+    text: &str,
+    day: u8,
+    entry: &str,
+    number: impl Fn(&str) -> Option<u8>,
+) -> Result<String, String> {
+    let mut first = None;
+    let mut after = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        match number(line) {
+            Some(n) if n == day => return Err(format!("day {day} is already listed")),
+            Some(n) if n < day => after = Some(offset + line.len()),
+            Some(_) => {
+                first.get_or_insert(offset);
+            }
+            None => {}
+        }
+        offset += line.len();
+    }
+
+    let at = after
+        .or(first)
+        .ok_or("found no days to add this one next to")?;
+    let mut text = text.to_string();
+    text.insert_str(at, entry);
+    Ok(text)
+}
+
+/// Add `day` to the `days!` invocation in the registry source.
+fn register(registry: &str, day: u8) -> Result<String, String> {
+    let entry = format!("    {day} => day_{day}::Day{day},\n");
+    insert_in_order(registry, day, &entry, |line| {
+        let (n, rest) = line.trim().split_once(" => ")?;
+        rest.starts_with("day_").then_some(())?;
+        n.parse().ok()
+    })
+}
+
+/// Add a dependency on `day`'s crate to the runner's manifest.
+fn depend(manifest: &str, day: u8) -> Result<String, String> {
+    let entry = format!("day-{day} = {{ path = \"../day-{day}\" }}\n");
+    insert_in_order(manifest, day, &entry, |line| {
+        let (n, _) = line.strip_prefix("day-")?.split_once(" = ")?;
+        n.parse().ok()
+    })
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|source| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Create `day-N/` under the workspace `root` and register it with the
+/// runner, returning the files written.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let dir = root.join(format!("day-{day}"));
+    if dir.exists() {
+        return Err(ScaffoldError::Exists(dir));
+    }
+
+    // Check the runner can take the day before touching anything.
+    let mut runner = Vec::new();
+    for (path, add) in [
+        (REGISTRY, register as fn(&str, u8) -> Result<String, String>),
+        (MANIFEST, depend),
+    ] {
+        let path = root.join(path);
+        let contents = add(&read(&path)?, day).map_err(|why| ScaffoldError::Registry {
+            path: path.clone(),
+            why,
+        })?;
+        runner.push((path, contents));
+    }
+
+    let src = dir.join("src");
+    fs::create_dir_all(&src).map_err(|source| ScaffoldError::Io {
+        path: src.clone(),
+        source,
+    })?;
+
+    let mut written = Vec::new();
+    for (path, contents) in [
+        (dir.join("Cargo.toml"), manifest(day)),
+        (src.join("lib.rs"), library(day)),
+        (src.join("main.rs"), binary(day)),
+    ]
+    .into_iter()
+    .chain(runner)
+    {
+        write(&path, &contents)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::scratch::ScratchDir;
+
+    const REGISTRY_SOURCE: &str = "use crate::execute::execute;
+
+days! {
+    1 => day_1::Day1,
+    3 => day_3::Day3,
+}
+";
+
+    const MANIFEST_SOURCE: &str = "[dependencies]
+clap = { version = \"4.4\", features = [\"derive\"] }
+common = { path = \"../common\" }
+day-1 = { path = \"../day-1\" }
+day-3 = { path = \"../day-3\" }
+";
+
+    #[test]
+    fn fills_in_template() {
+        let source = library(12);
+        assert!(source.starts_with("//! Day 12\n"));
+        assert!(source.contains("pub struct Day12;"));
+        assert!(source.contains("const DAY: u8 = 12;"));
+        assert!(!source.contains("DayN"));
+        assert!(binary(12).contains("common::run::<day_12::Day12>();"));
+    }
+
+    #[test]
+    fn registers_in_order() {
+        let registry = register(REGISTRY_SOURCE, 2).unwrap();
+        assert!(registry.contains(
+            "    1 => day_1::Day1,
+    2 => day_2::Day2,
+    3 => day_3::Day3,"
+        ));
+
+        let registry = register(REGISTRY_SOURCE, 12).unwrap();
+        assert!(registry.ends_with(
+            "    3 => day_3::Day3,
+    12 => day_12::Day12,
+}
+"
+        ));
+
+        assert!(register(REGISTRY_SOURCE, 3).is_err());
+        assert!(register("fn main() {}", 3).is_err());
+    }
+
+    #[test]
+    fn depends_in_order() {
+        let manifest = depend(MANIFEST_SOURCE, 2).unwrap();
+        assert!(manifest.ends_with(
+            "day-1 = { path = \"../day-1\" }
+day-2 = { path = \"../day-2\" }
+day-3 = { path = \"../day-3\" }
+"
+        ));
+        assert!(depend(MANIFEST_SOURCE, 1).is_err());
+    }
+
+    #[test]
+    fn refuses_to_overwrite() {
+        let root = ScratchDir::new("new");
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join(REGISTRY), REGISTRY_SOURCE).unwrap();
+        fs::write(root.join(MANIFEST), MANIFEST_SOURCE).unwrap();
+
+        let written = scaffold(&root, 2).unwrap();
+        assert_eq!(written.len(), 5);
+        assert_eq!(
+            fs::read_to_string(root.join("day-2/src/lib.rs")).unwrap(),
+            library(2)
+        );
+        assert!(fs::read_to_string(root.join(REGISTRY))
+            .unwrap()
+            .contains("day_2::Day2"));
+        assert!(fs::read_to_string(root.join(MANIFEST))
+            .unwrap()
+            .contains("day-2 = "));
+
+        fs::write(root.join("day-2/src/lib.rs"), "// solved").unwrap();
+        assert!(matches!(scaffold(&root, 2), Err(ScaffoldError::Exists(_))));
+        assert_eq!(
+            fs::read_to_string(root.join("day-2/src/lib.rs")).unwrap(),
+            "// solved"
+        );
+
+        // Registered but without a directory is refused too, before
+        // anything is written.
+        assert!(matches!(
+            scaffold(&root, 1),
+            Err(ScaffoldError::Registry { .. })
+        ));
+        assert!(!root.join("day-1").exists());
+    }
+}
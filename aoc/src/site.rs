@@ -0,0 +1,377 @@
+//! Talking to the Advent of Code site: downloading puzzle inputs into the
+//! directory the runner reads them from, and the requests that
+//! [`submit`](crate::submit) makes.
+
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The session cookie from a logged in browser, taking priority over the
+/// config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Overrides [`DEFAULT_URL`], for testing against a local server.
+pub const URL_VAR: &str = "AOC_URL";
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// The site asks automated tools to say who they are.
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"), " (ureq)");
+
+#[derive(Debug)]
+pub enum SiteError {
+    /// Neither the variable nor the config file has a session token.
+    NoSession {
+        config: Option<PathBuf>,
+    },
+    /// The site answered, but not with what was asked for.
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+    /// The request got no answer at all.
+    Transport {
+        url: String,
+        why: String,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Display for SiteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SiteError::NoSession { config } => {
+                write!(f, "No session token: set ${SESSION_VAR}")?;
+                if let Some(path) = config {
+                    write!(f, " or write it to {}", path.display())?;
+                }
+                Ok(())
+            }
+            SiteError::Status { url, status, body } => {
+                write!(f, "{url} answered {status}")?;
+                if let Some(line) = body.lines().map(str::trim).find(|l| !l.is_empty()) {
+                    write!(f, ": {line}")?;
+                }
+                if matches!(status, 400 | 401 | 500) {
+                    write!(f, " (is the session token still valid?)")?;
+                }
+                Ok(())
+            }
+            SiteError::Transport { url, why } => write!(f, "Failed to reach {url}: {why}"),
+            SiteError::Io { path, source } => {
+                write!(f, "Failed to access {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SiteError {}
+
+/// Where the session token is kept when it isn't in the environment:
+/// `aoc/session` in `$XDG_CONFIG_HOME`, or in `~/.config` without it.
+pub fn config_path() -> Option<PathBuf> {
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(dir.join("aoc").join("session"))
+}
+
+/// The session token from `var`, or else the file at `config`. Either may
+/// hold the bare token or `session=<token>` as copied from a cookie.
+fn session(var: Option<OsString>, config: Option<PathBuf>) -> Result<String, SiteError> {
+    let clean = |token: &str| {
+        let token = token.trim();
+        token.strip_prefix("session=").unwrap_or(token).to_string()
+    };
+
+    if let Some(token) = var.as_ref().and_then(|v| v.to_str()) {
+        if !token.trim().is_empty() {
+            return Ok(clean(token));
+        }
+    }
+
+    if let Some(path) = &config {
+        match fs::read_to_string(path) {
+            Ok(text) if !text.trim().is_empty() => return Ok(clean(&text)),
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => {
+                return Err(SiteError::Io {
+                    path: path.clone(),
+                    source,
+                })
+            }
+        }
+    }
+
+    Err(SiteError::NoSession { config })
+}
+
+/// A logged in connection to the site.
+pub struct Client {
+    base: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base: &str, session: &str) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    /// A client for `$AOC_URL` or the real site, with the session token from
+    /// `$AOC_SESSION` or the config file.
+    pub fn from_env() -> Result<Self, SiteError> {
+        let token = session(env::var_os(SESSION_VAR), config_path())?;
+        let base = env::var(URL_VAR).unwrap_or_else(|_| DEFAULT_URL.to_string());
+        Ok(Self::new(&base, &token))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base)
+    }
+
+    /// The body of the page at `path`, which starts with a `/`.
+    pub fn get(&self, path: &str) -> Result<String, SiteError> {
+        let url = self.url(path);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        Self::body(url, response)
+    }
+
+    /// The body of the page answering `fields` posted as a form to `path`.
+    pub fn post(&self, path: &str, fields: &[(&str, &str)]) -> Result<String, SiteError> {
+        let url = self.url(path);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(fields);
+        Self::body(url, response)
+    }
+
+    fn body(
+        url: String,
+        response: Result<ureq::Response, ureq::Error>,
+    ) -> Result<String, SiteError> {
+        match response {
+            Ok(response) => response.into_string().map_err(|e| SiteError::Transport {
+                url,
+                why: e.to_string(),
+            }),
+            Err(ureq::Error::Status(status, response)) => Err(SiteError::Status {
+                url,
+                status,
+                body: response.into_string().unwrap_or_default(),
+            }),
+            Err(e) => Err(SiteError::Transport {
+                url,
+                why: e.to_string(),
+            }),
+        }
+    }
+}
+
+/// The directory inputs are saved to: `$AOC_INPUT_DIR`, or `inputs`.
+pub fn input_dir() -> PathBuf {
+    env::var_os(common::input::INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("inputs"))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already saved, so the site wasn't asked.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Save `day`'s input to `dir`, where the runner looks for it, unless it's
+/// already there.
+///
+/// `connect` is only called when the input has to be downloaded, so a
+/// cached input needs no session token.
+pub fn fetch(
+    day: u8,
+    dir: &Path,
+    connect: impl FnOnce() -> Result<Client, SiteError>,
+) -> Result<Fetched, SiteError> {
+    let path = dir.join(format!("day-{day:02}.txt"));
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = connect()?.get(&format!("/{}/day/{day}/input", common::YEAR))?;
+
+    // Written aside and moved into place, so an interrupted download is
+    // never mistaken for a cached input
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| SiteError::Io { path, source }
+    };
+    let partial = dir.join(format!("day-{day:02}.txt.part"));
+    fs::create_dir_all(dir).map_err(io_error(dir))?;
+    fs::write(&partial, input).map_err(io_error(&partial))?;
+    fs::rename(&partial, &path).map_err(io_error(&path))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use common::scratch::ScratchDir;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// A local server that gives each of `responses` as a status and body
+    /// to one connection in turn, then stops. Joining it gives each request
+    /// it got: the head and then the body.
+    pub(crate) fn serve(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request.push_str(&line);
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                io::Read::read_exact(&mut reader, &mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        (base, handle)
+    }
+
+    #[test]
+    fn downloads_once() {
+        let scratch = ScratchDir::new("fetch");
+        // Made by the fetch
+        let dir = scratch.join("inputs");
+        let (base, server) = serve(vec![(200, "0 3 6\n1 3 6\n".to_string())]);
+
+        let fetched = fetch(9, &dir, || Ok(Client::new(&base, "abc123"))).unwrap();
+        let path = dir.join("day-09.txt");
+        assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "0 3 6\n1 3 6\n");
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with(&format!("GET /{}/day/9/input HTTP/1.1", common::YEAR)));
+        assert!(request.to_lowercase().contains("cookie: session=abc123"));
+        assert!(request.contains("aoc-runner/"));
+
+        // The server has stopped, so this can only work from the cache
+        let fetched = fetch(9, &dir, || panic!("connected for a cached input")).unwrap();
+        assert_eq!(fetched, Fetched::Cached(path));
+    }
+
+    #[test]
+    fn failures_cache_nothing() {
+        let dir = ScratchDir::new("fetch-fail");
+        let (base, server) = serve(vec![
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!".to_string(),
+            ),
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                    .to_string(),
+            ),
+        ]);
+        let client = || Ok(Client::new(&base, "abc123"));
+
+        let err = fetch(25, &dir, client).unwrap_err();
+        assert!(
+            matches!(err, SiteError::Status { status: 404, .. }),
+            "{err}"
+        );
+        let err = fetch(25, &dir, client).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "{base}/{}/day/25/input answered 400: Puzzle inputs differ by user.  Please log in to get your puzzle input. (is the session token still valid?)",
+                common::YEAR
+            )
+        );
+        server.join().unwrap();
+        assert!(!dir.join("day-25.txt").exists());
+
+        // Nothing is listening any more
+        let err = fetch(25, &dir, client).unwrap_err();
+        assert!(matches!(err, SiteError::Transport { .. }), "{err}");
+    }
+
+    #[test]
+    fn finds_session() {
+        let dir = ScratchDir::new("session");
+        let config = dir.join("session");
+
+        let missing = session(None, Some(config.clone())).unwrap_err();
+        assert!(missing.to_string().contains(&config.display().to_string()));
+
+        fs::write(&config, "session=from-file\n").unwrap();
+        assert_eq!(session(None, Some(config.clone())).unwrap(), "from-file");
+        assert_eq!(
+            session(Some(" from-env ".into()), Some(config.clone())).unwrap(),
+            "from-env"
+        );
+        // An empty variable doesn't hide the file
+        assert_eq!(session(Some("".into()), Some(config)).unwrap(), "from-file");
+        assert!(matches!(
+            session(None, None),
+            Err(SiteError::NoSession { config: None })
+        ));
+    }
+}
@@ -0,0 +1,477 @@
+//! Submitting answers to the site, and a log of how earlier submissions
+//! went so answers already known to be wrong aren't sent again.
+//!
+//! The log has one submission per line: year, day, part, the verdict and the
+//! answer, separated by spaces. Blank lines and lines starting with `#` are
+//! ignored.
+
+use crate::site::{Client, SiteError};
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Where submissions are logged unless `--log` says otherwise.
+pub const DEFAULT_PATH: &str = "submissions.txt";
+
+/// What the site made of an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way.
+    Wrong,
+    /// Another answer was sent too recently, so this one wasn't checked.
+    Wait {
+        seconds: u64,
+    },
+    /// The part isn't open, or is already solved.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Read the verdict from the page the site answered with, or `None` if
+    /// it isn't one of the pages it's known to send.
+    pub fn from_page(html: &str) -> Option<Self> {
+        let text = page_text(html);
+
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if text.contains("You gave an answer too recently") {
+            let seconds = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .and_then(|(left, _)| duration(left))?;
+            Some(Verdict::Wait { seconds })
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+
+    /// The word used in the log, for the verdicts that say something about
+    /// the answer.
+    fn word(self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too-high"),
+            Verdict::TooLow => Some("too-low"),
+            Verdict::Wrong => Some("wrong"),
+            Verdict::Wait { .. } | Verdict::WrongLevel => None,
+        }
+    }
+
+    fn from_word(word: &str) -> Option<Self> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+        ]
+        .into_iter()
+        .find(|v| v.word() == Some(word))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait { seconds } => write!(f, "not checked, wait {seconds}s first"),
+            Verdict::WrongLevel => write!(f, "not checked, the part is locked or already solved"),
+        }
+    }
+}
+
+/// The words in the page's `<article>`, or the whole page without one,
+/// with the tags taken out.
+fn page_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(inside, _)| inside);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A time such as `1m 30s` in seconds.
+fn duration(text: &str) -> Option<u64> {
+    text.split_whitespace().try_fold(0, |total: u64, part| {
+        let unit = match part.chars().last()? {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let n: u64 = part[..part.len() - 1].parse().ok()?;
+        total.checked_add(n.checked_mul(unit)?)
+    })
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The log already says how this answer would do.
+    Known {
+        answer: String,
+        why: String,
+    },
+    /// The site answered with a page this doesn't understand.
+    Unrecognised {
+        page: String,
+    },
+    Site(SiteError),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// A line that isn't `<year> <day> <part> <verdict> <answer>`.
+    Malformed {
+        path: PathBuf,
+        line: usize,
+    },
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::Known { answer, why } => {
+                write!(f, "Not submitting {answer}: {why}")
+            }
+            SubmitError::Unrecognised { page } => {
+                let text = page_text(page);
+                let excerpt: String = text.chars().take(200).collect();
+                write!(f, "The site's reply wasn't a verdict: {excerpt}")
+            }
+            SubmitError::Site(e) => e.fmt(f),
+            SubmitError::Io { path, source } => {
+                write!(f, "Failed to access {}: {source}", path.display())
+            }
+            SubmitError::Malformed { path, line } => write!(
+                f,
+                "{}:{line}: expected `<year> <day> <part> <verdict> <answer>`",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<SiteError> for SubmitError {
+    fn from(e: SiteError) -> Self {
+        SubmitError::Site(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    puzzle: Puzzle,
+    verdict: Verdict,
+    answer: String,
+}
+
+/// Every answer submitted that the site gave a verdict on, oldest first.
+#[derive(Debug, Default)]
+pub struct Log {
+    entries: Vec<Entry>,
+}
+
+impl Log {
+    /// Read the log at `path`. A missing file has nothing in it yet.
+    pub fn load(path: &Path) -> Result<Self, SubmitError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|line| SubmitError::Malformed {
+                path: path.to_path_buf(),
+                line,
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(SubmitError::Io {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SubmitError> {
+        fs::write(path, self.to_string()).map_err(|source| SubmitError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Parse the file format, or return the number of the first bad line.
+    fn parse(text: &str) -> Result<Self, usize> {
+        let mut entries = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(5, ' ');
+            let mut field = || fields.next().ok_or(i + 1);
+            let puzzle = Puzzle {
+                year: field()?.parse().map_err(|_| i + 1)?,
+                day: field()?.parse().map_err(|_| i + 1)?,
+                part: field()?.parse().map_err(|_| i + 1)?,
+            };
+            let verdict = Verdict::from_word(field()?).ok_or(i + 1)?;
+            entries.push(Entry {
+                puzzle,
+                verdict,
+                answer: field()?.to_string(),
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Why submitting `answer` would be pointless, if the log shows it is:
+    /// the part is solved, or the answer was wrong before, or it's beyond
+    /// one that was too high or too low.
+    pub fn check(&self, puzzle: Puzzle, answer: &str) -> Option<String> {
+        let earlier = self.entries.iter().filter(|e| e.puzzle == puzzle);
+        let number = answer.parse::<i128>().ok();
+
+        for entry in earlier {
+            let bound = number.zip(entry.answer.parse::<i128>().ok());
+            match entry.verdict {
+                Verdict::Correct if entry.answer == answer => {
+                    return Some("it was already accepted".to_string())
+                }
+                Verdict::Correct => {
+                    return Some(format!(
+                        "part {} was solved with {}",
+                        puzzle.part, entry.answer
+                    ))
+                }
+                _ if entry.answer == answer => {
+                    return Some(format!("it was already {}", entry.verdict))
+                }
+                Verdict::TooHigh if bound.is_some_and(|(n, high)| n > high) => {
+                    return Some(format!("{} was already too high", entry.answer))
+                }
+                Verdict::TooLow if bound.is_some_and(|(n, low)| n < low) => {
+                    return Some(format!("{} was already too low", entry.answer))
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Log a verdict, unless it says nothing about the answer.
+    pub fn record(&mut self, puzzle: Puzzle, verdict: Verdict, answer: &str) {
+        if verdict.word().is_some() {
+            self.entries.push(Entry {
+                puzzle,
+                verdict,
+                answer: answer.to_string(),
+            });
+        }
+    }
+}
+
+impl Display for Log {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# year day part verdict answer")?;
+        for entry in &self.entries {
+            let Puzzle { year, day, part } = entry.puzzle;
+            let word = entry.verdict.word().unwrap_or_default();
+            writeln!(f, "{year} {day:02} {part} {word} {}", entry.answer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Send `answer` for `puzzle` unless `log` rules it out, and log the
+/// verdict.
+///
+/// `connect` is only called if the answer is sent.
+pub fn submit(
+    puzzle: Puzzle,
+    answer: &str,
+    log: &mut Log,
+    connect: impl FnOnce() -> Result<Client, SiteError>,
+) -> Result<Verdict, SubmitError> {
+    if let Some(why) = log.check(puzzle, answer) {
+        return Err(SubmitError::Known {
+            answer: answer.to_string(),
+            why,
+        });
+    }
+
+    let path = format!("/{}/day/{}/answer", puzzle.year, puzzle.day);
+    let level = puzzle.part.to_string();
+    let page = connect()?.post(&path, &[("level", &level), ("answer", answer)])?;
+    let verdict = Verdict::from_page(&page).ok_or(SubmitError::Unrecognised { page })?;
+
+    log.record(puzzle, verdict, answer);
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::site::tests::serve;
+
+    const PART_1: Puzzle = Puzzle {
+        year: 2023,
+        day: 6,
+        part: 1,
+    };
+
+    fn page(article: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html><head><title>Day 6 - Advent of Code 2023</title></head>\
+             <body><main>\n<article><p>{article}</p></article>\n</main></body></html>"
+        )
+    }
+
+    #[test]
+    fn verdicts() {
+        let cases = [
+            ("That's the right answer!  You are <em>one gold star</em> closer to restoring snow operations. <a href=\"/2023/day/6#part2\">[Continue to Part Two]</a>", Some(Verdict::Correct)),
+            ("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again. <a href=\"/2023/day/6\">[Return to Day 6]</a>", Some(Verdict::TooHigh)),
+            ("That's not the right answer; your answer is too low.", Some(Verdict::TooLow)),
+            ("That's not the right answer.  If you're stuck, make sure you're using the full input data.", Some(Verdict::Wrong)),
+            ("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 36s left to wait.", Some(Verdict::Wait { seconds: 36 })),
+            ("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 2s left to wait.", Some(Verdict::Wait { seconds: 242 })),
+            ("You don't seem to be solving the right level.  Did you already complete it?", Some(Verdict::WrongLevel)),
+            ("Something else entirely", None),
+        ];
+        for (article, verdict) in cases {
+            assert_eq!(Verdict::from_page(&page(article)), verdict, "{article}");
+        }
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let mut log = Log::default();
+        assert_eq!(log.check(PART_1, "50"), None);
+
+        log.record(PART_1, Verdict::TooHigh, "100");
+        log.record(PART_1, Verdict::TooLow, "20");
+        log.record(PART_1, Verdict::Wrong, "forty");
+        log.record(PART_1, Verdict::Wait { seconds: 30 }, "60");
+
+        assert_eq!(log.check(PART_1, "100").unwrap(), "it was already too high");
+        assert_eq!(
+            log.check(PART_1, "150").unwrap(),
+            "100 was already too high"
+        );
+        assert_eq!(log.check(PART_1, "-3").unwrap(), "20 was already too low");
+        assert_eq!(log.check(PART_1, "forty").unwrap(), "it was already wrong");
+        // A wait says nothing about the answer
+        assert_eq!(log.check(PART_1, "60"), None);
+        assert_eq!(log.check(Puzzle { part: 2, ..PART_1 }, "150"), None);
+
+        log.record(PART_1, Verdict::Correct, "42");
+        assert_eq!(log.check(PART_1, "42").unwrap(), "it was already accepted");
+        assert_eq!(
+            log.check(PART_1, "43").unwrap(),
+            "part 1 was solved with 42"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut log = Log::default();
+        log.record(PART_1, Verdict::TooLow, "20");
+        log.record(Puzzle { part: 2, ..PART_1 }, Verdict::Correct, "SOME TEXT");
+
+        let text = log.to_string();
+        assert_eq!(
+            text,
+            "# year day part verdict answer
+2023 06 1 too-low 20
+2023 06 2 correct SOME TEXT
+"
+        );
+        assert_eq!(Log::parse(&text).unwrap().entries, log.entries);
+        assert_eq!(Log::parse("2023 6 1 maybe 4").unwrap_err(), 1);
+    }
+
+    #[test]
+    fn submits_and_logs() {
+        let (base, server) = serve(vec![
+            (200, page("That's not the right answer; your answer is too low.")),
+            (200, page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m left to wait.")),
+            (200, page("That's the right answer!")),
+            (200, "<html>Down for maintenance</html>".to_string()),
+        ]);
+        let client = || Ok(Client::new(&base, "abc123"));
+        let mut log = Log::default();
+
+        assert_eq!(
+            submit(PART_1, "20", &mut log, client).unwrap(),
+            Verdict::TooLow
+        );
+        // Refused without asking the site
+        let err = submit(PART_1, "19", &mut log, || {
+            panic!("submitted a known wrong answer")
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Not submitting 19: 20 was already too low");
+
+        assert_eq!(
+            submit(PART_1, "30", &mut log, client).unwrap(),
+            Verdict::Wait { seconds: 60 }
+        );
+        assert_eq!(
+            submit(PART_1, "30", &mut log, client).unwrap(),
+            Verdict::Correct
+        );
+
+        let part_2 = Puzzle { part: 2, ..PART_1 };
+        let err = submit(part_2, "7", &mut log, client).unwrap_err();
+        assert!(matches!(err, SubmitError::Unrecognised { .. }), "{err}");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/6/answer HTTP/1.1"));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("cookie: session=abc123"));
+        assert!(requests[0].ends_with("level=1&answer=20"));
+        assert!(requests[3].ends_with("level=2&answer=7"));
+
+        assert_eq!(
+            log.to_string(),
+            "# year day part verdict answer
+2023 06 1 too-low 20
+2023 06 1 correct 30
+"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(str::to_string).collect())
+}
+
+pub fn part_1(input: &[String]) -> Result<u64, SolveError> {
+    let _ = input;
+    Err(SolveError::NoSolution("part 1 isn't solved yet".to_string()))
+}
+
+pub fn part_2(input: &[String]) -> Result<u64, SolveError> {
+    let _ = input;
+    Err(SolveError::NoSolution("part 2 isn't solved yet".to_string()))
+}
+
+pub struct DayN;
+
+impl Solution for DayN {
+    const DAY: u8 = N;
+
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::input;
+
+    const INPUT: &str = "
+        ";
+
+    #[test]
+    #[ignore = "paste the example into INPUT and fill in its answer"]
+    fn p1() {
+        assert_eq!(part_1(&parse(&input::example(INPUT)).unwrap()), Ok(0));
+    }
+
+    #[test]
+    #[ignore = "paste the example into INPUT and fill in its answer"]
+    fn p2() {
+        assert_eq!(part_2(&parse(&input::example(INPUT)).unwrap()), Ok(0));
+    }
+}
@@ -0,0 +1,85 @@
+use num_bigint::BigInt;
+use std::fmt::Display;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(BigInt),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(n) => n.fmt(f),
+            Answer::Unsigned(n) => n.fmt(f),
+            Answer::Big(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($variant:ident: $($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::$variant(n.into())
+            }
+        })*
+    };
+}
+
+from_int!(Signed: i8, i16, i32, i64);
+from_int!(Unsigned: u8, u16, u32, u64);
+from_int!(Big: i128, u128, BigInt);
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Signed(n as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Unsigned(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Answer::from(-3isize), Answer::Signed(-3));
+        assert_eq!(Answer::from(3usize), Answer::Unsigned(3));
+        assert_eq!(Answer::from(7u32), Answer::Unsigned(7));
+        assert_eq!(
+            Answer::from(1u128 << 100),
+            Answer::Big(BigInt::from(1u128 << 100))
+        );
+        assert_eq!(Answer::from("EZ"), Answer::Text("EZ".to_string()));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(-42i64).to_string(), "-42");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("ABC").to_string(), "ABC");
+    }
+}
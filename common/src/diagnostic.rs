@@ -0,0 +1,119 @@
+//! Rendering a [`ParseError`] against the input it came from.
+
+use crate::ParseError;
+use std::fmt::Write;
+
+impl ParseError {
+    /// Show the offending line of `source` with the bad span underlined, in
+    /// the style of a compiler diagnostic.
+    ///
+    /// `source` must be the whole input the error was reported for.
+    pub fn render(&self, source: &str) -> String {
+        let text = source.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = self.line.to_string().len();
+        let pad = " ".repeat(gutter);
+
+        let before: String = text
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = self.found.lines().next().unwrap_or("").chars().count();
+        let width = width.min(text.chars().count().saturating_sub(self.column - 1));
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            "error: expected {}, found {}",
+            self.expected,
+            self.found_text()
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{pad}--> day {}, line {}, column {}",
+            self.day, self.line, self.column
+        )
+        .unwrap();
+        writeln!(out, "{pad} |").unwrap();
+        writeln!(out, "{:>gutter$} | {text}", self.line).unwrap();
+        write!(out, "{pad} | {before}{}", "^".repeat(width.max(1))).unwrap();
+
+        if let Some(hint) = self.hint() {
+            write!(out, "\n{pad} |\n{pad} = hint: {hint}").unwrap();
+        }
+
+        out
+    }
+
+    fn hint(&self) -> Option<&str> {
+        match &self.hint {
+            Some(hint) => Some(hint),
+            None if self.found.is_empty() => Some("the line ends early, is the input truncated?"),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn underlines_span() {
+        let source = "Game 1: 3 blue\nGame 2: 1 bleu, 2 red";
+        let err = ParseError::new(2, source, &source[25..29], "`red`, `green` or `blue`");
+
+        assert_eq!(
+            err.render(source),
+            r#"error: expected `red`, `green` or `blue`, found "bleu"
+ --> day 2, line 2, column 11
+  |
+2 | Game 2: 1 bleu, 2 red
+  |           ^^^^"#
+        );
+    }
+
+    #[test]
+    fn missing_text() {
+        let source = "Game 1: 3 blue\nGame 7:";
+        let err = ParseError::new(2, source, &source[source.len()..], "`<count> <colour>`");
+
+        assert_eq!(
+            err.render(source),
+            "error: expected `<count> <colour>`, found end of line
+ --> day 2, line 2, column 8
+  |
+2 | Game 7:
+  |        ^
+  |
+  = hint: the line ends early, is the input truncated?"
+        );
+    }
+
+    #[test]
+    fn wide_gutter_and_hint() {
+        let source = ".....\n".repeat(11) + ".S-X.";
+        let found = &source[source.len() - 2..source.len() - 1];
+        let err = ParseError::new(10, &source, found, "a pipe").with_hint("pipes are `|`, `-`");
+
+        assert_eq!(
+            err.render(&source),
+            r#"error: expected a pipe, found "X"
+  --> day 10, line 12, column 4
+   |
+12 | .S-X.
+   |    ^
+   |
+   = hint: pipes are `|`, `-`"#
+        );
+    }
+
+    #[test]
+    fn span_stops_at_end_of_line() {
+        let source = "seeds: 79 14\nsoil map:\n50 98";
+        let err = ParseError::new(5, source, &source[13..], "`seed-to-soil map:`");
+
+        assert!(err.render(source).ends_with("2 | soil map:\n  | ^^^^^^^^^"));
+    }
+}
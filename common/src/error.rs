@@ -0,0 +1,186 @@
+use std::{fmt::Display, str::FromStr};
+
+/// Where and why a day's input failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line in the input.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// What the parser was looking for.
+    pub expected: String,
+    /// The offending text, empty when the line ended too early.
+    pub found: String,
+    /// Advice on how to fix the input, if the parser has any.
+    pub hint: Option<String>,
+}
+
+impl ParseError {
+    /// Report that `found` isn't what was `expected`.
+    ///
+    /// `found` must be a slice of `source`, which is used to work out the
+    /// line and column. Use an empty slice at the end of `source` for
+    /// something that is missing.
+    pub fn new(day: u8, source: &str, found: &str, expected: impl Into<String>) -> Self {
+        let offset = (found.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+        debug_assert!(
+            offset <= source.len(),
+            "{found:?} is not part of {source:?}"
+        );
+        let before = &source[..offset.min(source.len())];
+
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+            found: found.to_string(),
+            hint: None,
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub(crate) fn found_text(&self) -> String {
+        if self.found.is_empty() {
+            "end of line".to_string()
+        } else {
+            format!("{:?}", self.found)
+        }
+    }
+
+    /// Move an error found while parsing a single line to that line's place
+    /// in the whole input.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line += line - 1;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {}",
+            self.day,
+            self.line,
+            self.column,
+            self.expected,
+            self.found_text()
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a part couldn't be solved from input that parsed fine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input doesn't make sense for the puzzle.
+    BadInput(String),
+    /// The search finished without finding an answer.
+    NoSolution(String),
+    /// A value grew too large for its type.
+    Overflow(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::BadInput(why) => write!(f, "bad input: {why}"),
+            SolveError::NoSolution(why) => write!(f, "no solution: {why}"),
+            SolveError::Overflow(what) => write!(f, "overflow: {what}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Parse `text`, a slice of `source`, as a number.
+pub fn parse_number<T: FromStr>(day: u8, source: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(day, source, text, "a number"))
+}
+
+/// Parse every line of `input`, keeping track of which line an error is on.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e: ParseError| e.on_line(i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position() {
+        let source = "Game 1: 3 blue\nGame 2: 1 bleu";
+        let err = ParseError::new(2, source, &source[25..], "a colour");
+
+        assert_eq!((err.line, err.column), (2, 11));
+        assert_eq!(err.found, "bleu");
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 2, column 11: expected a colour, found \"bleu\""
+        );
+    }
+
+    #[test]
+    fn missing() {
+        let line = "Game 7";
+        let err = ParseError::new(2, line, &line[line.len()..], "`:`").on_line(7);
+
+        assert_eq!((err.line, err.column), (7, 7));
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 7, column 7: expected `:`, found end of line"
+        );
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let line = "→→ x";
+        let err = ParseError::new(0, line, &line[line.len() - 1..], "y");
+        assert_eq!(err.column, 4);
+    }
+
+    #[test]
+    fn numbers() {
+        let line = "seeds: 79 1x4";
+        assert_eq!(parse_number::<u64>(5, line, &line[7..9]), Ok(79));
+
+        let err = parse_number::<u64>(5, line, &line[10..]).unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (11, "1x4"));
+    }
+
+    #[derive(Debug)]
+    struct Digit(u32);
+
+    impl FromStr for Digit {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            parse_number(0, s, s).map(Digit)
+        }
+    }
+
+    #[test]
+    fn lines() {
+        let digits: Vec<Digit> = parse_lines("1\n2\n3").unwrap();
+        assert_eq!(digits.iter().map(|d| d.0).sum::<u32>(), 6);
+
+        let err = parse_lines::<Digit>("1\n2\nthree").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+}
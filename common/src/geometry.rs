@@ -0,0 +1,254 @@
+//! Points and directions on a grid, with rows growing downwards.
+
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A position or an offset, as `(row, column)`. Unsigned points are
+/// positions on a [`Grid`](crate::grid::Grid), signed ones are offsets or
+/// positions on an unbounded plane.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub row: T,
+    pub column: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(row: T, column: T) -> Self {
+        Self { row, column }
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((row, column): (T, T)) -> Self {
+        Self { row, column }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.row, point.column)
+    }
+}
+
+/// `(row, column)`, as 0-based indices.
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.column)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.row + other.row, self.column + other.column)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.row - other.row, self.column - other.column)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.row += other.row;
+        self.column += other.column;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.row -= other.row;
+        self.column -= other.column;
+    }
+}
+
+/// Scaling, such as an offset times a number of steps.
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.row * factor, self.column * factor)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.row, -self.column)
+    }
+}
+
+macro_rules! metrics {
+    ($($t:ty => $distance:ty),*) => {$(
+        impl Point<$t> {
+            /// Steps between the points moving along rows and columns.
+            pub fn manhattan(self, other: Self) -> $distance {
+                self.row.abs_diff(other.row) + self.column.abs_diff(other.column)
+            }
+
+            /// Steps between the points moving diagonally too.
+            pub fn chebyshev(self, other: Self) -> $distance {
+                self.row
+                    .abs_diff(other.row)
+                    .max(self.column.abs_diff(other.column))
+            }
+        }
+    )*};
+}
+
+metrics!(usize => usize, isize => usize, u32 => u32, i32 => u32, u64 => u64, i64 => u64);
+
+impl Point<usize> {
+    /// `self` moved by `offset`, or `None` if that's above or left of the
+    /// origin.
+    pub fn checked_add_signed(self, offset: Point<isize>) -> Option<Self> {
+        Some(Self::new(
+            self.row.checked_add_signed(offset.row)?,
+            self.column.checked_add_signed(offset.column)?,
+        ))
+    }
+
+    /// The next point in `direction`, or `None` if that's off the top or
+    /// left edge. [`Grid::step`](crate::grid::Grid::step) checks the other
+    /// two edges as well.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.checked_add_signed(direction.offset())
+    }
+
+    pub fn signed(self) -> Option<Point<isize>> {
+        Some(Point::new(
+            self.row.try_into().ok()?,
+            self.column.try_into().ok()?,
+        ))
+    }
+}
+
+impl Point<isize> {
+    /// The eight offsets to the points sharing an edge or a corner,
+    /// clockwise from up.
+    pub const AROUND: [Self; 8] = [
+        Self::new(-1, 0),
+        Self::new(-1, 1),
+        Self::new(0, 1),
+        Self::new(1, 1),
+        Self::new(1, 0),
+        Self::new(1, -1),
+        Self::new(0, -1),
+        Self::new(-1, -1),
+    ];
+
+    /// The next point in `direction`.
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// `None` if either coordinate is negative.
+    pub fn unsigned(self) -> Option<Point<usize>> {
+        Some(Point::new(
+            self.row.try_into().ok()?,
+            self.column.try_into().ok()?,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turn_right().reverse()
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// One step this way.
+    pub fn offset(self) -> Point<isize> {
+        match self {
+            Self::Up => Point::new(-1, 0),
+            Self::Right => Point::new(0, 1),
+            Self::Down => Point::new(1, 0),
+            Self::Left => Point::new(0, -1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut a = Point::new(3, -2);
+        assert_eq!(a + Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(a - Point::new(1, 1), Point::new(2, -3));
+        assert_eq!(a * 3, Point::new(9, -6));
+        a += Direction::Down.offset();
+        assert_eq!(a, Point::new(4, -2));
+        a -= Point::new(4, -2);
+        assert_eq!(a, Point::default());
+        assert_eq!(<(i32, i32)>::from(Point::from((1, 2))), (1, 2));
+        assert_eq!(Point::new(1, -2).to_string(), "(1, -2)");
+    }
+
+    #[test]
+    fn metrics() {
+        let a = Point::new(1usize, 6);
+        let b = Point::new(5, 2);
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (8, 4));
+
+        let c = Point::new(-3isize, 2);
+        assert_eq!((c.manhattan(Point::default()), c.chebyshev(c)), (5, 0));
+    }
+
+    #[test]
+    fn stepping() {
+        let origin = Point::new(0usize, 0);
+        assert_eq!(origin.step(Direction::Up), None);
+        assert_eq!(origin.step(Direction::Left), None);
+        assert_eq!(origin.step(Direction::Right), Some(Point::new(0, 1)));
+        assert_eq!(Point::new(0isize, 0).step(Direction::Up), Point::new(-1, 0));
+
+        assert_eq!(Point::new(-1isize, 2).unsigned(), None);
+        assert_eq!(Point::new(1isize, 2).unsigned(), Some(Point::new(1, 2)));
+        assert_eq!(Point::new(usize::MAX, 0).signed(), None);
+    }
+}
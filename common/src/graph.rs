@@ -0,0 +1,397 @@
+//! Directed graphs whose nodes are looked up by value but stored by index.
+
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+/// The index of a node, handed out in the order nodes are added.
+pub type NodeId = usize;
+
+/// A directed graph with a value per node and a label per edge.
+///
+/// Each node's value is stored once and given a [`NodeId`]; everything else
+/// works with those. For an undirected graph add each edge both ways, or use
+/// [`Graph::connect`].
+#[derive(Debug, Clone)]
+pub struct Graph<N, E = ()> {
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    /// Outgoing edges, in the order they were added.
+    edges: Vec<Vec<(NodeId, E)>>,
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N: Eq + Hash + Clone, E> Graph<N, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `node`, adding it if it's new.
+    pub fn intern(&mut self, node: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    pub fn id<Q>(&self, node: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(node).copied()
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// # Panics
+    ///
+    /// If either node isn't in the graph.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, label: E) {
+        assert!(to < self.nodes.len(), "no node {to} in the graph");
+        self.edges[from].push((to, label));
+    }
+
+    /// Add an edge each way between `a` and `b`.
+    pub fn connect(&mut self, a: NodeId, b: NodeId, label: E)
+    where
+        E: Clone,
+    {
+        self.add_edge(a, b, label.clone());
+        self.add_edge(b, a, label);
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    /// Every node with its id, in id order.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The edges leaving `id`, in the order they were added.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, E)] {
+        &self.edges[id]
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|&(to, _)| to)
+    }
+
+    /// The fewest edges from `start` to each node, indexed by id: `None`
+    /// for the ones it can't reach.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        distances[start] = Some(0);
+        let mut queue = VecDeque::from([(start, 0)]);
+
+        while let Some((id, distance)) = queue.pop_front() {
+            for next in self.neighbors(id) {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Every node reachable from `start`, each before the ones found
+    /// through it.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(id) = stack.pop() {
+            if std::mem::replace(&mut seen[id], true) {
+                continue;
+            }
+            order.push(id);
+            // Reversed so that the first edge is followed first
+            stack.extend(
+                self.neighbors(id)
+                    .filter(|&n| !seen[n])
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev(),
+            );
+        }
+
+        order
+    }
+
+    /// The groups of nodes joined by edges in either direction, each in id
+    /// order, ordered by their smallest id.
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut undirected = vec![Vec::new(); self.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, _) in edges {
+                undirected[from].push(to);
+                undirected[to].push(from);
+            }
+        }
+
+        let mut component = vec![None; self.len()];
+        let mut components: Vec<Vec<NodeId>> = Vec::new();
+        for start in 0..self.len() {
+            if component[start].is_some() {
+                continue;
+            }
+
+            let index = components.len();
+            let mut members = vec![start];
+            component[start] = Some(index);
+            let mut i = 0;
+            while let Some(&id) = members.get(i) {
+                for &next in &undirected[id] {
+                    if component[next].is_none() {
+                        component[next] = Some(index);
+                        members.push(next);
+                    }
+                }
+                i += 1;
+            }
+
+            members.sort_unstable();
+            components.push(members);
+        }
+
+        components
+    }
+
+    /// Some cycle of directed edges, as the nodes along it, or `None` if
+    /// there are none. A node with an edge to itself is a cycle of one.
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        for root in 0..self.len() {
+            if state[root] != State::New {
+                continue;
+            }
+
+            // The current path, with how many of each node's edges are done
+            let mut path = vec![(root, 0)];
+            state[root] = State::OnPath;
+            while let Some((id, edge)) = path.last_mut() {
+                let id = *id;
+                let Some(&(next, _)) = self.edges[id].get(*edge) else {
+                    state[id] = State::Done;
+                    path.pop();
+                    continue;
+                };
+                *edge += 1;
+
+                match state[next] {
+                    State::New => {
+                        state[next] = State::OnPath;
+                        path.push((next, 0));
+                    }
+                    State::OnPath => {
+                        let from = path.iter().position(|&(n, _)| n == next)?;
+                        return Some(path[from..].iter().map(|&(n, _)| n).collect());
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The nodes ordered so that every edge goes forwards, or a cycle that
+    /// makes that impossible.
+    pub fn topological_order(&self) -> Result<Vec<NodeId>, Vec<NodeId>> {
+        let mut incoming = vec![0usize; self.len()];
+        for to in self.edges.iter().flatten().map(|&(to, _)| to) {
+            incoming[to] += 1;
+        }
+
+        let mut ready: VecDeque<NodeId> = (0..self.len()).filter(|&id| incoming[id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for next in self.neighbors(id) {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err(self.find_cycle().expect("nodes left over are on a cycle"))
+        }
+    }
+
+    /// The graph in Graphviz's DOT language, with each node labelled by its
+    /// value and each edge by what `label` gives for it, if anything.
+    pub fn to_dot(&self, name: &str, label: impl Fn(&E) -> Option<String>) -> String
+    where
+        N: Display,
+    {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+
+        let mut dot = format!("digraph {} {{\n", quote(name));
+        for (id, node) in self.nodes() {
+            writeln!(dot, "    n{id} [label={}];", quote(&node.to_string())).unwrap();
+        }
+        for (from, edges) in self.edges.iter().enumerate() {
+            for (to, edge) in edges {
+                match label(edge) {
+                    Some(text) => writeln!(dot, "    n{from} -> n{to} [label={}];", quote(&text)),
+                    None => writeln!(dot, "    n{from} -> n{to};"),
+                }
+                .unwrap();
+            }
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `edges` between nodes named by single letters, added in order.
+    fn graph(edges: &[(&'static str, &'static str)]) -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        for &(from, to) in edges {
+            let (from, to) = (graph.intern(from), graph.intern(to));
+            graph.add_edge(from, to, ());
+        }
+        graph
+    }
+
+    #[test]
+    fn interning() {
+        let mut graph: Graph<String, char> = Graph::new();
+        let a = graph.intern("a".to_string());
+        let b = graph.intern("b".to_string());
+        assert_eq!(graph.intern("a".to_string()), a);
+        assert_eq!((graph.id("b"), graph.id("c")), (Some(b), None));
+        assert_eq!(graph.node(b), "b");
+
+        graph.connect(a, b, 'x');
+        assert_eq!(graph.edges(a), [(b, 'x')]);
+        assert_eq!(graph.neighbors(b).collect::<Vec<_>>(), [a]);
+        assert_eq!(graph.len(), 2);
+    }
+
+    #[test]
+    fn searches() {
+        let g = graph(&[
+            ("a", "b"),
+            ("a", "c"),
+            ("b", "d"),
+            ("c", "d"),
+            ("d", "e"),
+            ("f", "a"),
+        ]);
+        let id = |name| g.id(name).unwrap();
+
+        let distances = g.bfs(id("a"));
+        assert_eq!(distances[id("d")], Some(2));
+        assert_eq!(distances[id("e")], Some(3));
+        assert_eq!(distances[id("f")], None);
+
+        let names: Vec<&str> = g.dfs(id("a")).iter().map(|&n| *g.node(n)).collect();
+        assert_eq!(names, ["a", "b", "d", "e", "c"]);
+    }
+
+    #[test]
+    fn components() {
+        let mut g = graph(&[("a", "b"), ("c", "b"), ("d", "e")]);
+        g.intern("f");
+        let names: Vec<Vec<&str>> = g
+            .components()
+            .iter()
+            .map(|c| c.iter().map(|&n| *g.node(n)).collect())
+            .collect();
+        assert_eq!(names, [vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]);
+    }
+
+    #[test]
+    fn cycles() {
+        let dag = graph(&[
+            ("shirt", "tie"),
+            ("tie", "jacket"),
+            ("trousers", "shoes"),
+            ("trousers", "jacket"),
+        ]);
+        assert_eq!(dag.find_cycle(), None);
+        let order = dag.topological_order().unwrap();
+        let position = |name| order.iter().position(|&n| n == dag.id(name).unwrap());
+        assert!(position("shirt") < position("tie"));
+        assert!(position("tie") < position("jacket"));
+        assert!(position("trousers") < position("shoes"));
+
+        let looped = graph(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "b")]);
+        let cycle: Vec<&str> = looped
+            .find_cycle()
+            .unwrap()
+            .iter()
+            .map(|&n| *looped.node(n))
+            .collect();
+        assert_eq!(cycle, ["b", "c", "d"]);
+        assert_eq!(looped.topological_order().unwrap_err().len(), 3);
+
+        assert_eq!(graph(&[("a", "a")]).find_cycle(), Some(vec![0]));
+    }
+
+    #[test]
+    fn dot() {
+        let mut g: Graph<&str, char> = Graph::new();
+        let (a, b) = (g.intern("a"), g.intern("say \"hi\""));
+        g.add_edge(a, b, 'L');
+        g.add_edge(b, b, 'R');
+
+        assert_eq!(
+            g.to_dot("day 8", |turn| (*turn == 'L').then(|| turn.to_string())),
+            r#"digraph "day 8" {
+    n0 [label="a"];
+    n1 [label="say \"hi\""];
+    n0 -> n1 [label="L"];
+    n1 -> n1;
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! A rectangular grid of cells, as drawn by most map-shaped puzzle inputs.
+
+use crate::{
+    geometry::{Direction, Point},
+    ParseError,
+};
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// A cell's position, counting from the top left.
+pub type Pos = Point<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row after row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A grid of `rows`, or `None` if they aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Read a grid with a cell per character of `input`.
+    ///
+    /// `cell` gets each cell's position and its character as a slice of
+    /// `input`, so it can point a [`ParseError`] at it. Rows of different
+    /// lengths are an error.
+    pub fn parse<F>(day: u8, input: &str, mut cell: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(Pos, &str) -> Result<T, ParseError>,
+    {
+        let mut rows = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let row = line
+                .char_indices()
+                .enumerate()
+                .map(|(j, (k, c))| cell(Pos::new(i, j), &line[k..k + c.len_utf8()]))
+                .collect::<Result<Vec<T>, _>>()?;
+
+            if let Some(first) = rows.first().map(Vec::len) {
+                if row.len() != first {
+                    let expected = format!("a row {first} characters wide");
+                    return Err(ParseError::new(day, input, line, &expected));
+                }
+            }
+            rows.push(row);
+        }
+
+        let Some(width) = rows.first().map(Vec::len) else {
+            return Err(ParseError::new(day, input, input, "a grid"));
+        };
+
+        Ok(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.column < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.row * self.width + pos.column])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.row * self.width + pos.column])
+    }
+
+    /// The position `offset` away from `pos`, if it's on the grid.
+    pub fn offset(&self, pos: Pos, offset: Point<isize>) -> Option<Pos> {
+        let pos = pos.checked_add_signed(offset)?;
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The next position in `direction`, if it's on the grid.
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        self.offset(pos, direction.offset())
+    }
+
+    /// The cells sharing an edge with `pos` that are on the grid, clockwise
+    /// from up.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// The cells sharing an edge or a corner with `pos` that are on the grid,
+    /// clockwise from up.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Point::AROUND
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| Pos::new(row, column)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, and a grid with no columns has no cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        let cells = if column < self.width {
+            &self.cells[column..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// The grid flipped over its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "{pos:?} is outside a grid {} wide and {} high",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside a grid {width} wide and {height} high"),
+        }
+    }
+}
+
+/// Draws the grid the way it's parsed: a line per row, a cell per column.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(0, input, |_, c| Ok(c.chars().next().unwrap())).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = chars("ab\ncd\nef");
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[Pos::new(2, 1)], 'f');
+        assert_eq!(grid.get(Pos::new(1, 2)), None);
+        assert_eq!(grid.to_string(), "ab\ncd\nef");
+
+        let err = Grid::parse(0, "ab\ncde", |_, _| Ok(())).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a row 2 characters wide");
+
+        let input = ".#\n#x";
+        let err = Grid::parse(0, input, |_, c| match c {
+            "." | "#" => Ok(c == "#"),
+            _ => Err(ParseError::new(0, input, c, "`.` or `#`")),
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        assert!(Grid::parse(0, "", |_, _| Ok(())).is_err());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, ());
+        let around = |neighbors: Vec<Pos>| -> Vec<(usize, usize)> {
+            neighbors.into_iter().map(Into::into).collect()
+        };
+        assert_eq!(
+            around(grid.neighbors4(Pos::new(1, 1)).collect()),
+            [(0, 1), (1, 2), (2, 1), (1, 0)]
+        );
+        assert_eq!(
+            around(grid.neighbors4(Pos::new(0, 0)).collect()),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(
+            around(grid.neighbors8(Pos::new(2, 2)).collect()),
+            [(1, 2), (2, 1), (1, 1)]
+        );
+        assert_eq!(grid.step(Pos::new(2, 1), Direction::Down), None);
+        assert_eq!(
+            grid.step(Pos::new(2, 1), Direction::Right),
+            Some(Pos::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = chars("abc\ndef");
+        let rows: Vec<String> = grid.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(grid.column(3).count(), 0);
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn iterate() {
+        let grid = chars("ab\ncd");
+        let cells: Vec<((usize, usize), char)> = grid.iter().map(|(p, c)| (p.into(), *c)).collect();
+        assert_eq!(
+            cells,
+            [((0, 0), 'a'), ((0, 1), 'b'), ((1, 0), 'c'), ((1, 1), 'd')]
+        );
+
+        let mut upper = grid.map(|c| c.to_ascii_uppercase());
+        upper[Pos::new(0, 0)] = 'z';
+        assert_eq!(upper.to_string(), "zB\nCD");
+        assert_eq!(Grid::from_rows(vec![vec![1], vec![2, 3]]), None);
+    }
+}
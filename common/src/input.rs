@@ -0,0 +1,286 @@
+//! Finding, reading and normalizing a day's puzzle input.
+
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Directory searched for `day-NN.txt` files before the defaults.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug)]
+pub enum InputError {
+    /// None of the candidate paths existed.
+    NotFound {
+        day: u8,
+        tried: Vec<PathBuf>,
+    },
+    /// A path existed but couldn't be read.
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Stdin(io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound { day, tried } => {
+                write!(f, "No input found for day {day}, tried:")?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Io { path, source } => {
+                write!(f, "Failed to read {}: {source}", path.display())
+            }
+            InputError::Stdin(e) => write!(f, "Failed to read stdin: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// The paths searched for `day`'s input when none is given explicitly, in
+/// order.
+///
+/// A bare `input.txt` could be any day's input, so it's only tried when
+/// `only_day` says `day` is the only one being read.
+pub fn candidates(day: u8, only_day: bool, input_dir: Option<&Path>) -> Vec<PathBuf> {
+    let file = format!("day-{day:02}.txt");
+    let mut paths = Vec::new();
+
+    if let Some(dir) = input_dir {
+        paths.push(dir.join(&file));
+    }
+    paths.push(Path::new("inputs").join(&file));
+    // Where inputs were saved before there was a runner.
+    paths.push(Path::new(&format!("day-{day}")).join("input.txt"));
+    if only_day {
+        paths.push(PathBuf::from("input.txt"));
+    }
+
+    paths
+}
+
+/// Read `day`'s input and [`normalize`] it.
+///
+/// `explicit` is a path given by the user, or `-` for stdin. Without one the
+/// directory in `AOC_INPUT_DIR` is searched, then the default locations; see
+/// [`candidates`] for `only_day`.
+pub fn read(day: u8, only_day: bool, explicit: Option<&str>) -> Result<String, InputError> {
+    let input_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    read_from(day, only_day, explicit, input_dir.as_deref(), io::stdin())
+}
+
+/// Read `day`'s input from the path given as the first command line
+/// argument, falling back to the default locations. Exits on failure.
+pub fn from_args(day: u8) -> String {
+    match read(day, true, env::args().nth(1).as_deref()) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Make text saved on any platform look the same to the parsers.
+///
+/// Line endings become `\n`, trailing whitespace is stripped from every
+/// line, trailing blank lines are dropped and so is a leading byte order
+/// mark. Leading whitespace is left alone.
+pub fn normalize(text: &str) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut lines: Vec<&str> = text.split('\n').map(str::trim_end).collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+/// Strip the indentation shared by an example block pasted into a test.
+///
+/// The first line is ignored when working out the indentation, since it
+/// usually starts right after the opening quote.
+pub fn dedent(text: &str) -> String {
+    let indent = text
+        .lines()
+        .skip(1)
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    text.lines()
+        .map(|l| {
+            let strip = indent.min(l.len() - l.trim_start().len());
+            &l[strip..]
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prepare an example from the puzzle text for a test: [`normalize`] it and
+/// [`dedent`] it.
+pub fn example(text: &str) -> String {
+    dedent(&normalize(text))
+}
+
+fn read_from(
+    day: u8,
+    only_day: bool,
+    explicit: Option<&str>,
+    input_dir: Option<&Path>,
+    stdin: impl Read,
+) -> Result<String, InputError> {
+    read_raw(day, only_day, explicit, input_dir, stdin).map(|text| normalize(&text))
+}
+
+fn read_raw(
+    day: u8,
+    only_day: bool,
+    explicit: Option<&str>,
+    input_dir: Option<&Path>,
+    mut stdin: impl Read,
+) -> Result<String, InputError> {
+    let tried = match explicit {
+        Some("-") => {
+            let mut text = String::new();
+            stdin.read_to_string(&mut text).map_err(InputError::Stdin)?;
+            return Ok(text);
+        }
+        Some(path) => vec![PathBuf::from(path)],
+        None => candidates(day, only_day, input_dir),
+    };
+
+    for path in &tried {
+        match fs::read_to_string(path) {
+            Ok(text) => return Ok(text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(source) => {
+                return Err(InputError::Io {
+                    path: path.clone(),
+                    source,
+                })
+            }
+        }
+    }
+
+    Err(InputError::NotFound { day, tried })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::ScratchDir;
+
+    #[test]
+    fn explicit_path() {
+        let dir = ScratchDir::new("input-explicit");
+        let path = dir.join("mine.txt");
+        fs::write(&path, "1abc2").unwrap();
+
+        let text = read_from(1, true, path.to_str(), None, io::empty()).unwrap();
+        assert_eq!(text, "1abc2");
+    }
+
+    #[test]
+    fn stdin() {
+        let text = read_from(3, true, Some("-"), None, "from stdin".as_bytes()).unwrap();
+        assert_eq!(text, "from stdin");
+    }
+
+    #[test]
+    fn input_dir_first() {
+        let dir = ScratchDir::new("input-dir");
+        fs::write(dir.join("day-07.txt"), "32T3K 765").unwrap();
+
+        let text = read_from(7, true, None, Some(&*dir), io::empty()).unwrap();
+        assert_eq!(text, "32T3K 765");
+        assert_eq!(candidates(7, true, Some(&*dir))[0], dir.join("day-07.txt"));
+    }
+
+    #[test]
+    fn lists_tried_paths() {
+        let dir = ScratchDir::new("input-missing");
+
+        let err = read_from(42, true, None, Some(&*dir), io::empty()).unwrap_err();
+        let message = err.to_string();
+        assert!(message.starts_with("No input found for day 42, tried:"));
+        for path in candidates(42, true, Some(&*dir)) {
+            assert!(message.contains(&path.display().to_string()));
+        }
+
+        let err = read_from(42, true, Some("nope.txt"), Some(&*dir), io::empty()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No input found for day 42, tried:\n  nope.txt"
+        );
+    }
+
+    #[test]
+    fn bare_input_only_for_one_day() {
+        let bare = PathBuf::from("input.txt");
+        assert_eq!(candidates(3, true, None).last(), Some(&bare));
+        assert!(!candidates(3, false, None).contains(&bare));
+    }
+
+    #[test]
+    fn normalizes_what_it_reads() {
+        let text = read_from(
+            5,
+            true,
+            Some("-"),
+            None,
+            "seeds: 79\r\n\r\nmap:\r\n".as_bytes(),
+        );
+        assert_eq!(text.unwrap(), "seeds: 79\n\nmap:");
+    }
+
+    #[test]
+    fn line_endings() {
+        let unix = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n";
+        let windows = "seeds: 79 14\r\n\r\nseed-to-soil map:\r\n50 98 2\r\n";
+        let old_mac = "seeds: 79 14\r\rseed-to-soil map:\r50 98 2\r";
+
+        assert_eq!(
+            normalize(unix),
+            "seeds: 79 14\n\nseed-to-soil map:\n50 98 2"
+        );
+        assert_eq!(normalize(windows), normalize(unix));
+        assert_eq!(normalize(old_mac), normalize(unix));
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let text = "\u{feff}RL  \n\t\nAAA = (BBB, CCC) \n\n\n";
+        assert_eq!(normalize(text), "RL\n\nAAA = (BBB, CCC)");
+        assert_eq!(normalize("  indented\n"), "  indented");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn dedents_examples() {
+        let block = "two1nine
+        eightwothree
+
+        abcone2threexyz
+          xtwone3four";
+
+        assert_eq!(
+            dedent(block),
+            "two1nine\neightwothree\n\nabcone2threexyz\n  xtwone3four"
+        );
+        assert_eq!(dedent("1abc2\npqr3stu8vwx"), "1abc2\npqr3stu8vwx");
+        assert_eq!(example("    a\r\n    b\r\n"), "a\nb");
+    }
+}
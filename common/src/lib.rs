@@ -0,0 +1,25 @@
+//! Pieces shared by every day and by the `aoc` runner.
+
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod math;
+pub mod numbers;
+pub mod parser;
+pub mod polynomial;
+pub mod ranges;
+pub mod scratch;
+pub mod trace;
+
+mod answer;
+mod diagnostic;
+mod error;
+mod solution;
+
+pub use answer::Answer;
+pub use error::{parse_lines, parse_number, ParseError, SolveError};
+pub use solution::{run, Solution};
+
+/// The Advent of Code event these are solutions for.
+pub const YEAR: u16 = 2023;
@@ -0,0 +1,362 @@
+//! Number theory for puzzles about things going round in cycles.
+
+use crate::SolveError;
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, or `None` if it doesn't fit in a `u64`. The
+/// least common multiple with 0 is 0.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// `(g, x, y)` such that `g` is the greatest common divisor of `a` and `b`
+/// and `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Every number `n` with `n % modulus == residue`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Congruence {
+    pub residue: u64,
+    pub modulus: u64,
+}
+
+impl Congruence {
+    /// `residue` is reduced, so `new(7, 5)` is the same as `new(2, 5)`.
+    ///
+    /// # Panics
+    ///
+    /// If `modulus` is 0.
+    pub fn new(residue: u64, modulus: u64) -> Self {
+        assert!(modulus > 0, "a congruence needs a modulus above 0");
+        Self {
+            residue: residue % modulus,
+            modulus,
+        }
+    }
+
+    /// The smallest solution that is at least `from`.
+    pub fn first_from(self, from: u64) -> Option<u64> {
+        if from <= self.residue {
+            return Some(self.residue);
+        }
+        let periods = (from - self.residue).div_ceil(self.modulus);
+        periods.checked_mul(self.modulus)?.checked_add(self.residue)
+    }
+}
+
+/// The numbers satisfying both congruences, which need not have coprime
+/// moduli: `None` if there are none.
+fn combine(a: Congruence, b: Congruence) -> Result<Option<Congruence>, SolveError> {
+    let (g, x, _) = extended_gcd(a.modulus.into(), b.modulus.into());
+    let diff = i128::from(b.residue) - i128::from(a.residue);
+    if diff % g != 0 {
+        return Ok(None);
+    }
+
+    let modulus = lcm(a.modulus, b.modulus)
+        .ok_or_else(|| SolveError::Overflow("the combined period".to_string()))?;
+
+    // a.residue + a.modulus * k solves both for k = x * diff / g, modulo
+    // b.modulus / g. Each factor is below 2^64 once reduced, so the
+    // products fit in a u128.
+    let step = i128::from(b.modulus) / g;
+    let reduce = |n: i128| n.rem_euclid(step) as u128;
+    let k = reduce(x) * reduce(diff / g) % step as u128;
+    let residue = (u128::from(a.residue) + u128::from(a.modulus) * k) % u128::from(modulus);
+
+    Ok(Some(Congruence {
+        residue: residue as u64,
+        modulus,
+    }))
+}
+
+/// The Chinese Remainder Theorem: every number satisfying all the
+/// congruences, as one congruence.
+///
+/// The moduli don't have to be coprime. It's a [`SolveError::NoSolution`] if
+/// the congruences contradict each other, and a [`SolveError::Overflow`] if
+/// the combined modulus doesn't fit in a `u64`. No congruences at all are
+/// satisfied by every number.
+pub fn crt(congruences: &[Congruence]) -> Result<Congruence, SolveError> {
+    congruences
+        .iter()
+        .try_fold(Congruence::new(0, 1), |acc, &c| {
+            combine(acc, c)?.ok_or_else(|| {
+                SolveError::NoSolution(format!(
+                    "no number is {} mod {} as well as those before it",
+                    c.residue, c.modulus
+                ))
+            })
+        })
+}
+
+/// Something that settles into a loop: the times it hits a target before
+/// `offset` happen once, and those from `offset` on repeat every `period`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// When the loop starts.
+    pub offset: u64,
+    pub period: u64,
+    /// The times of the hits before `offset + period`.
+    pub hits: Vec<u64>,
+}
+
+impl Cycle {
+    pub fn hits_at(&self, time: u64) -> bool {
+        if time < self.offset {
+            return self.hits.contains(&time);
+        }
+        let phase = (time - self.offset) % self.period;
+        self.hits.contains(&(self.offset + phase))
+    }
+
+    /// The hits that repeat, as congruences on the time.
+    fn repeating(&self) -> impl Iterator<Item = Congruence> + '_ {
+        self.hits
+            .iter()
+            .filter(|&&hit| hit >= self.offset)
+            .map(|&hit| Congruence::new(hit, self.period))
+    }
+}
+
+/// The first time every cycle hits at once.
+///
+/// Times before the last loop starts are checked one by one. After that each
+/// combination of repeating hits is a [`crt`] problem, so this is slow if
+/// the cycles have many hits each.
+pub fn first_alignment(cycles: &[Cycle]) -> Result<u64, SolveError> {
+    let Some(settled) = cycles.iter().map(|c| c.offset).max() else {
+        return Ok(0);
+    };
+    if let Some(time) = (0..settled).find(|&t| cycles.iter().all(|c| c.hits_at(t))) {
+        return Ok(time);
+    }
+
+    // One repeating hit from each cycle at a time
+    let mut combinations: Vec<Vec<Congruence>> = vec![Vec::new()];
+    for cycle in cycles {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                cycle.repeating().map(move |hit| {
+                    let mut combination = combination.clone();
+                    combination.push(hit);
+                    combination
+                })
+            })
+            .collect();
+    }
+
+    let mut first: Option<u64> = None;
+    let mut overflowed = None;
+    for combination in combinations {
+        match crt(&combination) {
+            Ok(aligned) => match aligned.first_from(settled) {
+                Some(time) => first = Some(first.map_or(time, |f| f.min(time))),
+                None => overflowed = Some(SolveError::Overflow("the aligned time".to_string())),
+            },
+            Err(SolveError::NoSolution(_)) => {}
+            Err(e) => overflowed = Some(e),
+        }
+    }
+
+    // An overflow only matters if nothing smaller was found
+    match (first, overflowed) {
+        (Some(time), _) => Ok(time),
+        (None, Some(e)) => Err(e),
+        (None, None) => Err(SolveError::NoSolution(
+            "the cycles never line up".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, 2), None);
+
+        for (a, b) in [(240, 46), (-12, 18), (7, 0), (0, 0), (35, -15)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g, "{a}, {b}");
+            assert_eq!(
+                g,
+                i128::from(gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64))
+            );
+        }
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        let solve = |pairs: &[(u64, u64)]| {
+            let congruences: Vec<Congruence> =
+                pairs.iter().map(|&(r, m)| Congruence::new(r, m)).collect();
+            crt(&congruences).map(|c| (c.residue, c.modulus))
+        };
+
+        assert_eq!(solve(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        // Not coprime, but consistent
+        assert_eq!(solve(&[(3, 4), (1, 6)]), Ok((7, 12)));
+        assert_eq!(solve(&[(0, 6), (0, 4)]), Ok((0, 12)));
+        assert!(matches!(
+            solve(&[(0, 4), (1, 6)]),
+            Err(SolveError::NoSolution(_))
+        ));
+        assert!(matches!(
+            solve(&[(1, u64::MAX), (0, u64::MAX - 1)]),
+            Err(SolveError::Overflow(_))
+        ));
+        assert_eq!(solve(&[]), Ok((0, 1)));
+
+        // Large coprime moduli whose product fits
+        let (a, b) = (4_294_967_291, 4_294_967_279);
+        let (r, m) = solve(&[(5, a), (7, b)]).unwrap();
+        assert_eq!((r % a, r % b, m), (5, 7, a * b));
+    }
+
+    #[test]
+    fn brute_force_agrees() {
+        for m1 in 1..10u64 {
+            for m2 in 1..10 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let expected = (0..m1 * m2).find(|n| n % m1 == r1 && n % m2 == r2);
+                        let found = crt(&[Congruence::new(r1, m1), Congruence::new(r2, m2)]);
+                        assert_eq!(found.ok().map(|c| c.residue), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn first_from() {
+        let c = Congruence::new(3, 5);
+        assert_eq!(c.first_from(0), Some(3));
+        assert_eq!(c.first_from(4), Some(8));
+        assert_eq!(c.first_from(8), Some(8));
+        assert_eq!(Congruence::new(0, u64::MAX).first_from(2), Some(u64::MAX));
+        assert_eq!(Congruence::new(5, u64::MAX - 1).first_from(u64::MAX), None);
+    }
+
+    #[test]
+    fn alignment() {
+        // The plain case: hits exactly at the end of each loop
+        let simple = |period| Cycle {
+            offset: 0,
+            period,
+            hits: vec![period - 1],
+        };
+        assert_eq!(first_alignment(&[simple(3), simple(5)]), Ok(14));
+
+        // Hits that aren't at the loop length, so plain LCM would be wrong
+        let a = Cycle {
+            offset: 2,
+            period: 4,
+            hits: vec![3],
+        };
+        let b = Cycle {
+            offset: 1,
+            period: 6,
+            hits: vec![5],
+        };
+        assert_eq!(first_alignment(&[a, b]), Ok(11));
+
+        // A hit before either loop starts
+        let early = Cycle {
+            offset: 5,
+            period: 3,
+            hits: vec![1, 6],
+        };
+        let every = Cycle {
+            offset: 0,
+            period: 1,
+            hits: vec![0],
+        };
+        assert_eq!(first_alignment(&[early, every]), Ok(1));
+
+        // Several hits per loop
+        let c = Cycle {
+            offset: 0,
+            period: 10,
+            hits: vec![2, 7],
+        };
+        let d = Cycle {
+            offset: 0,
+            period: 4,
+            hits: vec![3],
+        };
+        assert_eq!(first_alignment(&[c, d]), Ok(7));
+
+        let never = Cycle {
+            offset: 0,
+            period: 2,
+            hits: vec![0],
+        };
+        let odd = Cycle {
+            offset: 0,
+            period: 4,
+            hits: vec![1],
+        };
+        assert!(matches!(
+            first_alignment(&[never, odd]),
+            Err(SolveError::NoSolution(_))
+        ));
+    }
+
+    #[test]
+    fn alignment_brute_force() {
+        let cycles = [
+            Cycle {
+                offset: 3,
+                period: 4,
+                hits: vec![0, 4, 6],
+            },
+            Cycle {
+                offset: 1,
+                period: 6,
+                hits: vec![2, 5],
+            },
+            Cycle {
+                offset: 0,
+                period: 9,
+                hits: vec![2, 8],
+            },
+        ];
+        let expected = (0..1000).find(|&t| cycles.iter().all(|c| c.hits_at(t)));
+        assert_eq!(first_alignment(&cycles).ok(), expected);
+    }
+}
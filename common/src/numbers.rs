@@ -0,0 +1,165 @@
+//! Pulling the integers out of text that isn't worth a full parser.
+
+use crate::ParseError;
+use std::{
+    num::{IntErrorKind, ParseIntError},
+    ops::Range,
+    str::FromStr,
+};
+
+/// The primitive integer types.
+pub trait Integer: FromStr<Err = ParseIntError> + Copy {
+    /// Whether a `-` in front of the digits belongs to the number.
+    const SIGNED: bool;
+    const NAME: &'static str;
+}
+
+macro_rules! integers {
+    ($signed:literal => $($t:ty),*) => {$(
+        impl Integer for $t {
+            const SIGNED: bool = $signed;
+            const NAME: &'static str = stringify!($t);
+        }
+    )*};
+}
+
+integers!(false => u8, u16, u32, u64, u128, usize);
+integers!(true => i8, i16, i32, i64, i128, isize);
+
+/// An integer and the bytes of the text it was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number<T> {
+    pub value: T,
+    pub span: Range<usize>,
+}
+
+/// `text`, a slice of `source`, as a `T`. Too many digits for the type is
+/// reported as such, anything else as not being a number.
+pub fn parse_integer<T: Integer>(day: u8, source: &str, text: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|e: ParseIntError| {
+        let expected = match e.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                format!("a number that fits in {}", T::NAME)
+            }
+            _ => "a number".to_string(),
+        };
+        ParseError::new(day, source, text, expected)
+    })
+}
+
+/// Every integer in `text`, a slice of `source`, whatever is between them.
+///
+/// A run of digits is a number, with a `-` straight before it making it
+/// negative if `T` is signed and the `-` doesn't follow a letter or digit,
+/// so `-3` is negative but `seed-to-soil 7-3` has a 7 and a 3.
+pub fn integers<T: Integer>(
+    day: u8,
+    source: &str,
+    text: &str,
+) -> Result<Vec<Number<T>>, ParseError> {
+    integers_separated_by(day, source, text, |_| true)
+}
+
+/// Like [`integers`], but only characters matching `separator` may come
+/// between the numbers. Anything else is an error pointing at it.
+pub fn integers_separated_by<T: Integer>(
+    day: u8,
+    source: &str,
+    text: &str,
+    separator: impl Fn(char) -> bool,
+) -> Result<Vec<Number<T>>, ParseError> {
+    let mut numbers = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    let mut previous: Option<char> = None;
+
+    while let Some((start, c)) = chars.next() {
+        let digit_next = chars.peek().is_some_and(|&(_, next)| next.is_ascii_digit());
+        let sign =
+            T::SIGNED && c == '-' && digit_next && !previous.is_some_and(char::is_alphanumeric);
+
+        if c.is_ascii_digit() || sign {
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, d)) = chars.peek().filter(|(_, d)| d.is_ascii_digit()) {
+                end = i + d.len_utf8();
+                chars.next();
+            }
+            let span = start..end;
+            let value = parse_integer(day, source, &text[span.clone()])?;
+            numbers.push(Number { value, span });
+            previous = text[..end].chars().next_back();
+        } else if separator(c) {
+            previous = Some(c);
+        } else {
+            // Everything up to the next separator or digit
+            let end = text[start..]
+                .find(|d: char| d.is_ascii_digit() || separator(d))
+                .map_or(text.len(), |i| start + i);
+            return Err(ParseError::new(day, source, &text[start..end], "a number"));
+        }
+    }
+
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<T: Integer>(text: &str) -> Vec<T> {
+        integers(0, text, text)
+            .unwrap()
+            .into_iter()
+            .map(|n| n.value)
+            .collect()
+    }
+
+    #[test]
+    fn extracts() {
+        let text = "467..114..*-35";
+        let found: Vec<Number<u32>> = integers(3, text, text).unwrap();
+        assert_eq!(
+            found,
+            [
+                Number {
+                    value: 467,
+                    span: 0..3
+                },
+                Number {
+                    value: 114,
+                    span: 5..8
+                },
+                Number {
+                    value: 35,
+                    span: 12..14
+                },
+            ]
+        );
+
+        assert_eq!(values::<i64>("0 3 -6 -9"), [0, 3, -6, -9]);
+        assert_eq!(values::<u64>("0 3 -6 -9"), [0, 3, 6, 9]);
+        // Dashes between words or numbers are hyphens
+        assert_eq!(values::<i32>("x-1 7-3, -(-2)"), [1, 7, 3, -2]);
+        assert_eq!(values::<i32>("--1 - 2"), [-1, 2]);
+        assert!(values::<u8>("no numbers here").is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let source = "1 2\n3 99999999999";
+        let err = integers::<u32>(3, source, &source[4..]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.found, "99999999999");
+        assert_eq!(err.expected, "a number that fits in u32");
+
+        let err = integers::<i8>(3, "-129", "-129").unwrap_err();
+        assert_eq!(err.expected, "a number that fits in i8");
+
+        let err = integers_separated_by::<i64>(9, "1 1x4  5", "1 1x4  5", char::is_whitespace)
+            .unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (4, "x"));
+        assert_eq!(err.expected, "a number");
+
+        let spaced = integers_separated_by::<i64>(9, " 10 -2 ", " 10 -2 ", char::is_whitespace);
+        assert_eq!(spaced.unwrap().len(), 2);
+    }
+}
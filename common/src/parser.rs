@@ -0,0 +1,661 @@
+//! Parser combinators that remember where they are in the input, so a
+//! failure points at the text that caused it.
+//!
+//! A parser is a function from a [`Cursor`] to what it read and the cursor
+//! after it. The functions here build the common ones, and any closure with
+//! the right signature is a parser too. [`parse`] runs one over a whole
+//! input, and [`parse_within`] over part of one, such as a line from
+//! [`split_sections`].
+//!
+//! Nothing skips whitespace unless it says so: [`spaced`], [`list`] and
+//! [`key_value`] skip spaces and tabs, and only [`lines`] and [`sections`]
+//! move past the end of a line.
+
+use crate::{
+    numbers::{parse_integer, Integer},
+    ParseError,
+};
+
+/// A position in a day's input.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    day: u8,
+    source: &'a str,
+    offset: usize,
+    /// Where the text being parsed stops, which may be short of the end
+    /// of `source`.
+    end: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(day: u8, source: &'a str) -> Self {
+        Self::within(day, source, source)
+    }
+
+    /// A cursor over just `text`, which must be a slice of `source`.
+    /// Positions in errors are still counted from the start of `source`.
+    pub fn within(day: u8, source: &'a str, text: &'a str) -> Self {
+        let offset = (text.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+        assert!(
+            offset + text.len() <= source.len(),
+            "{text:?} is not part of {source:?}"
+        );
+        Self {
+            day,
+            source,
+            offset,
+            end: offset + text.len(),
+        }
+    }
+
+    /// Everything from here to the end of the text being parsed.
+    pub fn rest(self) -> &'a str {
+        &self.source[self.offset..self.end]
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.offset == self.end
+    }
+
+    /// Whether nothing is left on this line.
+    pub fn at_line_end(self) -> bool {
+        matches!(self.rest().chars().next(), None | Some('\n'))
+    }
+
+    fn advance(self, len: usize) -> Self {
+        Self {
+            offset: self.offset + len,
+            ..self
+        }
+    }
+
+    /// The text from here up to `later`.
+    pub fn consumed(self, later: Self) -> &'a str {
+        &self.source[self.offset..later.offset]
+    }
+
+    /// An error about `found`, which must be a slice of the input. For
+    /// things that parsed but make no sense, such as an unknown name.
+    pub fn error(self, found: &'a str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.day, self.source, found, expected)
+    }
+
+    /// An error at this position, showing the token that's here instead.
+    pub fn expected(self, expected: impl Into<String>) -> ParseError {
+        self.error(token(self.rest()), expected)
+    }
+
+    /// Whether `error` is at this position, so whatever failed read
+    /// nothing first.
+    fn is_at(self, error: &ParseError) -> bool {
+        let here = self.expected("");
+        (error.line, error.column) == (here.line, here.column)
+    }
+}
+
+/// The token at the start of `text`: a word or number with an optional
+/// sign, or else a single character. Empty at the end of a line.
+fn token(text: &str) -> &str {
+    let sign = usize::from(text.starts_with(['-', '+']));
+    let word = text[sign..]
+        .find(|c: char| !c.is_alphanumeric())
+        .unwrap_or(text.len() - sign);
+    if word > 0 {
+        return &text[..sign + word];
+    }
+
+    match text.chars().next() {
+        Some(c) if c != '\n' => &text[..c.len_utf8()],
+        _ => &text[..0],
+    }
+}
+
+fn starts_token(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '+')
+}
+
+/// What a parser read and where it stopped, or why it couldn't.
+pub type PResult<'a, T> = Result<(T, Cursor<'a>), ParseError>;
+
+pub trait Parser<'a, T> {
+    fn run(&self, input: Cursor<'a>) -> PResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Cursor<'a>) -> PResult<'a, T>,
+{
+    fn run(&self, input: Cursor<'a>) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Run `parser` over the whole of `source`, which may only have whitespace
+/// left over.
+pub fn parse<'a, T>(day: u8, source: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    parse_within(day, source, source, parser)
+}
+
+/// Run `parser` over all of `text`, a slice of `source`, as [`parse`] does.
+pub fn parse_within<'a, T>(
+    day: u8,
+    source: &'a str,
+    text: &'a str,
+    parser: impl Parser<'a, T>,
+) -> Result<T, ParseError> {
+    let (value, rest) = parser.run(Cursor::within(day, source, text))?;
+    let trailing = rest.rest().trim_start();
+    if trailing.is_empty() {
+        Ok(value)
+    } else {
+        Err(rest.error(token(trailing), "the end of the input"))
+    }
+}
+
+/// Exactly `text`.
+pub fn literal<'a>(text: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Cursor<'a>| {
+        if input.rest().starts_with(text) {
+            let after = input.advance(text.len());
+            Ok((input.consumed(after), after))
+        } else {
+            Err(input.expected(format!("`{text}`")))
+        }
+    }
+}
+
+/// The first of `options` whose text is next, as its value.
+pub fn one_of<'a, T: Clone, const N: usize>(options: [(&'static str, T); N]) -> impl Parser<'a, T> {
+    let names: Vec<String> = options
+        .iter()
+        .map(|(text, _)| format!("`{text}`"))
+        .collect();
+    let expected = match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {last}", rest.join(", ")),
+        _ => names.concat(),
+    };
+
+    move |input: Cursor<'a>| {
+        options
+            .iter()
+            .find(|(text, _)| input.rest().starts_with(text))
+            .map(|(text, value)| (value.clone(), input.advance(text.len())))
+            .ok_or_else(|| input.expected(expected.as_str()))
+    }
+}
+
+/// A number with an optional sign. Letters straight after the digits are
+/// part of it, so `6l` is a bad number rather than 6 followed by `l`.
+pub fn integer<'a, T: Integer>() -> impl Parser<'a, T> {
+    move |input: Cursor<'a>| {
+        let text = token(input.rest());
+        let value = parse_integer(input.day, input.source, text)?;
+        Ok((value, input.advance(text.len())))
+    }
+}
+
+/// A run of letters and digits.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    move |input: Cursor<'a>| {
+        let (text, after) = take_while(char::is_alphanumeric).run(input)?;
+        if text.is_empty() {
+            Err(input.expected("a word"))
+        } else {
+            Ok((text, after))
+        }
+    }
+}
+
+/// The characters up to the first that doesn't match `predicate`, which
+/// may be none of them.
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |input: Cursor<'a>| {
+        let rest = input.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        let after = input.advance(len);
+        Ok((input.consumed(after), after))
+    }
+}
+
+/// Any number of spaces and tabs.
+pub fn spaces<'a>() -> impl Parser<'a, ()> {
+    move |input: Cursor<'a>| {
+        let (_, after) = take_while(|c| c == ' ' || c == '\t').run(input)?;
+        Ok(((), after))
+    }
+}
+
+/// `parser` after any spaces.
+pub fn spaced<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    preceded(spaces(), parser)
+}
+
+/// A newline, or the end of the input.
+pub fn line_end<'a>() -> impl Parser<'a, ()> {
+    move |input: Cursor<'a>| {
+        if input.rest().starts_with('\n') {
+            Ok(((), input.advance(1)))
+        } else if input.is_empty() {
+            Ok(((), input))
+        } else {
+            Err(input.expected("the end of the line"))
+        }
+    }
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: Cursor<'a>| {
+        let (value, after) = parser.run(input)?;
+        Ok((f(value), after))
+    }
+}
+
+/// `parser`'s value passed through `f`, which can reject it with what it
+/// expected instead. The error points at all the text `parser` read.
+pub fn try_map<'a, T, U>(
+    parser: impl Parser<'a, T>,
+    f: impl Fn(T) -> Result<U, String>,
+) -> impl Parser<'a, U> {
+    move |input: Cursor<'a>| {
+        let (value, after) = parser.run(input)?;
+        match f(value) {
+            Ok(value) => Ok((value, after)),
+            Err(expected) => Err(input.error(input.consumed(after), expected)),
+        }
+    }
+}
+
+/// `parser`'s value along with the text it read.
+pub fn spanned<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, (T, &'a str)> {
+    move |input: Cursor<'a>| {
+        let (value, after) = parser.run(input)?;
+        Ok(((value, input.consumed(after)), after))
+    }
+}
+
+/// Report a failure as `expected`, if `parser` failed before reading
+/// anything. Failures further in are more precise, so they're kept.
+pub fn label<'a, T>(parser: impl Parser<'a, T>, expected: &'static str) -> impl Parser<'a, T> {
+    move |input: Cursor<'a>| {
+        parser.run(input).map_err(|e| {
+            if input.is_at(&e) {
+                input.expected(expected)
+            } else {
+                e
+            }
+        })
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: Cursor<'a>| {
+        let (a, input) = first.run(input)?;
+        let (b, input) = second.run(input)?;
+        Ok(((a, b), input))
+    }
+}
+
+/// `parser` after `before`, keeping only what `parser` read.
+pub fn preceded<'a, A, T>(
+    before: impl Parser<'a, A>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    map(pair(before, parser), |(_, value)| value)
+}
+
+/// `parser` followed by `after`, keeping only what `parser` read.
+pub fn terminated<'a, T, B>(
+    parser: impl Parser<'a, T>,
+    after: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    map(pair(parser, after), |(value, _)| value)
+}
+
+pub fn delimited<'a, A, T, B>(
+    before: impl Parser<'a, A>,
+    parser: impl Parser<'a, T>,
+    after: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    preceded(before, terminated(parser, after))
+}
+
+/// `parser` if it matches here, otherwise nothing.
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: Cursor<'a>| match parser.run(input) {
+        Ok((value, after)) => Ok((Some(value), after)),
+        Err(e) if input.is_at(&e) => Ok((None, input)),
+        Err(e) => Err(e),
+    }
+}
+
+/// `parser` as many times in a row as it matches, at least once.
+pub fn many1<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: Cursor<'a>| {
+        let (first, mut input) = parser.run(input)?;
+        let mut items = vec![first];
+        loop {
+            match parser.run(input) {
+                Ok((value, after)) if after.offset > input.offset => {
+                    items.push(value);
+                    input = after;
+                }
+                Ok(_) => return Ok((items, input)),
+                Err(e) if input.is_at(&e) => return Ok((items, input)),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// One or more `item`s with a `separator` between each.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: Cursor<'a>| {
+        let (first, mut input) = item.run(input)?;
+        let mut items = vec![first];
+        loop {
+            match separator.run(input) {
+                Ok((_, after)) => {
+                    let (value, after) = item.run(after)?;
+                    items.push(value);
+                    input = after;
+                }
+                Err(e) if input.is_at(&e) => return Ok((items, input)),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Any number of `item`s separated by spaces, up to the end of the line or
+/// something that can't start one, such as punctuation.
+pub fn list<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Cursor<'a>| {
+        let mut items = Vec::new();
+        loop {
+            let (_, after) = spaces().run(input)?;
+            if !starts_token(after.rest()) {
+                return Ok((items, after));
+            }
+            let (value, after) = item.run(after)?;
+            items.push(value);
+            input = after;
+        }
+    }
+}
+
+/// `key`, then `separator` and `value` with optional spaces before each,
+/// as in `Time: 7 15 30` or `AAA = (BBB, CCC)`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    separator: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    pair(key, preceded(spaced(literal(separator)), spaced(value)))
+}
+
+/// One `item` per line, each filling its line, up to a blank line or the
+/// end of the input. That blank line is left for the caller.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Cursor<'a>| {
+        let mut items = Vec::new();
+        loop {
+            let (value, after) = item.run(input)?;
+            if !after.at_line_end() {
+                return Err(after.expected("the end of the line"));
+            }
+            items.push(value);
+
+            let (_, next) = line_end().run(after)?;
+            if next.at_line_end() {
+                return Ok((items, after));
+            }
+            input = next;
+        }
+    }
+}
+
+/// One or more `section`s with blank lines between them.
+pub fn sections<'a, T>(section: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: Cursor<'a>| {
+        let (first, mut input) = section.run(input)?;
+        let mut items = vec![first];
+        loop {
+            let rest = input.rest();
+            let gap = rest.len() - rest.trim_start_matches('\n').len();
+            // Trailing newlines end the input rather than start a section
+            if gap < 2 || gap == rest.len() {
+                return Ok((items, input));
+            }
+            let (value, after) = section.run(input.advance(gap))?;
+            items.push(value);
+            input = after;
+        }
+    }
+}
+
+/// A block of lines with blank lines around it, named by its first line,
+/// such as `seed-to-soil map:` and the mappings under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The first line, as written.
+    pub header: &'a str,
+    /// The lines after the header.
+    pub body: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    /// The header and then the body, for a block that has no header.
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> + '_ {
+        std::iter::once(self.header).chain(self.body.iter().copied())
+    }
+}
+
+/// The sections of `source`, in order.
+///
+/// Lines can end in `\n` or `\r\n`, which are left off the lines given
+/// back, and a line of nothing but whitespace counts as blank. Every line
+/// is a slice of `source`, so it can be handed to [`parse_within`].
+pub fn split_sections(source: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut current: Option<Section> = None;
+
+    for line in source.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() {
+            sections.extend(current.take());
+            continue;
+        }
+
+        match &mut current {
+            Some(section) => section.body.push(line),
+            None => {
+                current = Some(Section {
+                    header: line,
+                    body: Vec::new(),
+                })
+            }
+        }
+    }
+    sections.extend(current);
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitives() {
+        assert_eq!(parse(1, "Game", literal("Game")), Ok("Game"));
+        assert_eq!(parse(1, "-42", integer()), Ok(-42i32));
+        assert_eq!(parse(1, "abc1 ", word()), Ok("abc1"));
+
+        let err = parse(1, "Card 1", literal("Game")).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (1, "`Game`"));
+        assert_eq!(err.found, "Card");
+
+        // Letters stuck to a number make it a bad number, not a short one
+        let err = parse(1, "6l", integer::<u32>()).unwrap_err();
+        assert_eq!(
+            (err.found.as_str(), err.expected.as_str()),
+            ("6l", "a number")
+        );
+        let err = parse(1, "300", integer::<u8>()).unwrap_err();
+        assert_eq!(err.expected, "a number that fits in u8");
+
+        let err = parse(1, "12 x", integer::<u32>()).unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (4, "the end of the input")
+        );
+    }
+
+    #[test]
+    fn choices() {
+        let colour = || one_of([("red", 'r'), ("green", 'g'), ("blue", 'b')]);
+        assert_eq!(parse(1, "green", colour()), Ok('g'));
+
+        let err = parse(1, "purple", colour()).unwrap_err();
+        assert_eq!(err.expected, "`red`, `green` or `blue`");
+        assert_eq!(err.found, "purple");
+
+        assert_eq!(parse(1, "x", opt(literal("y"))).ok(), None);
+        assert_eq!(
+            parse(1, "yx", pair(opt(literal("y")), opt(literal("x")))),
+            Ok((Some("y"), Some("x")))
+        );
+    }
+
+    #[test]
+    fn lists() {
+        let numbers = || {
+            pair(
+                list(integer::<u32>()),
+                preceded(literal("|"), list(integer::<u32>())),
+            )
+        };
+        assert_eq!(
+            parse(1, " 41 48 |  83  6", numbers()),
+            Ok((vec![41, 48], vec![83, 6]))
+        );
+        assert_eq!(parse(1, "|", numbers()), Ok((vec![], vec![])));
+
+        let err = parse(1, "41 48 83", numbers()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (9, "`|`"));
+
+        let games = separated(separated(spaced(word()), literal(",")), literal(";"));
+        assert_eq!(
+            parse(1, "a, b; c", games),
+            Ok(vec![vec!["a", "b"], vec!["c"]])
+        );
+        assert_eq!(
+            parse(1, "LRL", many1(one_of([("L", 0), ("R", 1)]))),
+            Ok(vec![0, 1, 0])
+        );
+    }
+
+    #[test]
+    fn key_values() {
+        let row = || key_value(literal("Time"), ":", list(integer::<u32>()));
+        assert_eq!(parse(1, "Time:   7  15", row()), Ok(("Time", vec![7, 15])));
+
+        let node = || {
+            key_value(
+                word(),
+                "=",
+                delimited(
+                    literal("("),
+                    pair(terminated(word(), literal(",")), spaced(word())),
+                    literal(")"),
+                ),
+            )
+        };
+        assert_eq!(
+            parse(1, "AAA = (BBB, CCC)", node()),
+            Ok(("AAA", ("BBB", "CCC")))
+        );
+    }
+
+    #[test]
+    fn line_structure() {
+        let blocks = || sections(lines(list(integer::<u32>())));
+        assert_eq!(
+            parse(1, "1 2\n3\n\n\n4\n", blocks()),
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4]]])
+        );
+
+        // Positions are counted across the whole input
+        let err = parse(1, "1 2\n3\n\n4 x", blocks()).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (4, 3, "x"));
+
+        let err = parse(1, "1 2\n3 |", blocks()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "the end of the line");
+    }
+
+    #[test]
+    fn labels() {
+        let cube = || {
+            label(
+                pair(integer::<u32>(), spaced(one_of([("red", 0), ("blue", 1)]))),
+                "`<count> <colour>`",
+            )
+        };
+
+        // Nothing matched, so the whole thing was missing
+        let err = parse(1, "", cube()).unwrap_err();
+        assert_eq!(err.expected, "`<count> <colour>`");
+
+        // Partway through, so the inner error says more
+        let err = parse(1, "4 purple", cube()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (3, "`red` or `blue`"));
+
+        let reject = try_map(word(), |w| Err::<(), _>(format!("not {w}")));
+        let err = parse(1, "4 purple", reject).unwrap_err();
+        assert_eq!((err.found.as_str(), err.expected.as_str()), ("4", "not 4"));
+    }
+
+    #[test]
+    fn named_sections() {
+        let source = "seeds: 79 14\r\n\r\nseed-to-soil map:\r\n50 98 2\r\n52 50 48\n \n\n\nsoil-to-fertilizer map:\n0 15 37\n";
+        let sections = split_sections(source);
+        let headers: Vec<&str> = sections.iter().map(|s| s.header).collect();
+        assert_eq!(
+            headers,
+            [
+                "seeds: 79 14",
+                "seed-to-soil map:",
+                "soil-to-fertilizer map:"
+            ]
+        );
+        assert_eq!(sections[0].body, Vec::<&str>::new());
+        assert_eq!(sections[1].body, ["50 98 2", "52 50 48"]);
+        assert_eq!(
+            sections[2].lines().collect::<Vec<_>>(),
+            ["soil-to-fertilizer map:", "0 15 37"]
+        );
+        assert!(split_sections("\n\n").is_empty());
+
+        // Lines parse on their own but report where they are in the whole
+        let numbers = || list(integer::<u32>());
+        assert_eq!(
+            parse_within(5, source, sections[1].body[1], numbers()),
+            Ok(vec![52, 50, 48])
+        );
+        let err = parse_within(
+            5,
+            source,
+            sections[2].body[0],
+            pair(numbers(), literal("x")),
+        )
+        .unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (10, 8, ""));
+    }
+}
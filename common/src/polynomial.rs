@@ -0,0 +1,358 @@
+//! Sequences generated by polynomials, found from their differences.
+//!
+//! A sequence's first differences are the gaps between its terms, its second
+//! differences the gaps between those, and so on. Every row of the pyramid
+//! this builds shrinks by one, and a polynomial of degree `d` has all zeros
+//! in row `d + 1`. The first term of each row is all it takes to rebuild the
+//! polynomial, so it can be evaluated at any index.
+
+use std::{
+    fmt::Display,
+    ops::{Add, Mul},
+};
+
+/// Every row of differences of a sequence, down to a row of zeros or, if
+/// the sequence is too short to reach one, an empty row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Differences {
+    rows: Vec<Vec<i64>>,
+}
+
+impl Differences {
+    /// The pyramid of `sequence`, or `None` if a difference overflows.
+    pub fn new(sequence: &[i64]) -> Option<Self> {
+        let mut rows = vec![sequence.to_vec()];
+        while let Some(last) = rows.last().filter(|row| row.iter().any(|&n| n != 0)) {
+            let next = last
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]))
+                .collect::<Option<_>>()?;
+            rows.push(next);
+        }
+
+        Some(Self { rows })
+    }
+
+    /// The sequence first, then each row of differences.
+    pub fn rows(&self) -> &[Vec<i64>] {
+        &self.rows
+    }
+
+    /// The polynomial the sequence follows, of the lowest degree possible.
+    pub fn polynomial(&self) -> Polynomial {
+        let mut newton: Vec<i64> = self
+            .rows
+            .iter()
+            .map_while(|row| row.first().copied())
+            .collect();
+        while newton.last() == Some(&0) {
+            newton.pop();
+        }
+
+        Polynomial { newton }
+    }
+
+    /// Whether the pyramid reached a row of zeros, so the sequence had more
+    /// terms than it takes to pin its polynomial down.
+    pub fn is_settled(&self) -> bool {
+        self.rows.last().is_some_and(|row| !row.is_empty())
+    }
+}
+
+/// Draws the pyramid with each row between the terms it's the difference of.
+impl Display for Differences {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .rows
+            .iter()
+            .flatten()
+            .map(|n| n.to_string().len())
+            .max()
+            .unwrap_or(1);
+        // Even, so that half a step lines up with a character
+        let step = (width + 2) / 2 * 2;
+
+        for (depth, row) in self.rows.iter().enumerate() {
+            if depth > 0 {
+                writeln!(f)?;
+            }
+            for (i, n) in row.iter().enumerate() {
+                let pad = if i == 0 {
+                    depth * step / 2
+                } else {
+                    step - width
+                };
+                write!(f, "{:pad$}{n:>width$}", "")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A polynomial with rational coefficients that is an integer at every
+/// integer, stored as the first term of each row of its [`Differences`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    /// Newton's forward differences at 0, so the value at `x` is the sum of
+    /// `newton[k] * binomial(x, k)`. No trailing zeros.
+    newton: Vec<i64>,
+}
+
+impl Polynomial {
+    /// The lowest degree polynomial whose value at each index `i` is
+    /// `sequence[i]`, or `None` if a difference overflows.
+    pub fn fit(sequence: &[i64]) -> Option<Self> {
+        Some(Differences::new(sequence)?.polynomial())
+    }
+
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.newton.len().checked_sub(1)
+    }
+
+    /// The value at index `x`, which can be past either end of the sequence
+    /// it was fitted to: `-1` is the term before the first. `None` if it
+    /// doesn't fit in an `i64`.
+    pub fn at(&self, x: i64) -> Option<i64> {
+        let x = i128::from(x);
+        let mut binomial: i128 = 1;
+        let mut sum: i128 = 0;
+
+        for (k, &d) in self.newton.iter().enumerate() {
+            if k > 0 {
+                // binomial(x, k) from binomial(x, k - 1); always divides
+                let k = k as i128;
+                binomial = binomial.checked_mul(x - (k - 1))? / k;
+            }
+            sum = sum.checked_add(binomial.checked_mul(d.into())?)?;
+        }
+
+        sum.try_into().ok()
+    }
+
+    /// The coefficients of `1, x, x², ...`, or `None` if working them out
+    /// overflows.
+    pub fn coefficients(&self) -> Option<Vec<Rational>> {
+        let mut coefficients = vec![Rational::from(0); self.newton.len()];
+        // x(x - 1)...(x - k + 1), lowest power first
+        let mut falling: Vec<i128> = vec![1];
+        let mut factorial: i128 = 1;
+
+        for (k, &d) in self.newton.iter().enumerate() {
+            if k > 0 {
+                let shift = i128::try_from(k - 1).ok()?;
+                let mut next = vec![0; falling.len() + 1];
+                for (j, &c) in falling.iter().enumerate() {
+                    next[j + 1] += c;
+                    next[j] = next[j].checked_sub(c.checked_mul(shift)?)?;
+                }
+                falling = next;
+                factorial = factorial.checked_mul(k.try_into().ok()?)?;
+            }
+
+            for (coefficient, &c) in coefficients.iter_mut().zip(&falling) {
+                let term = Rational::new(c.checked_mul(d.into())?, factorial);
+                *coefficient = coefficient.checked_add(term)?;
+            }
+        }
+
+        Some(coefficients)
+    }
+}
+
+/// A fraction in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl Rational {
+    /// # Panics
+    ///
+    /// If `denominator` is 0.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "a fraction can't have a denominator of 0");
+        let sign = denominator.signum();
+        let g = gcd(numerator, denominator).max(1);
+        Self {
+            numerator: sign * numerator / g,
+            denominator: sign * denominator / g,
+        }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let g = gcd(self.denominator, other.denominator);
+        let denominator = (self.denominator / g).checked_mul(other.denominator)?;
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator / g)?
+            .checked_add(other.numerator.checked_mul(self.denominator / g)?)?;
+        Some(Self::new(numerator, denominator))
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // Cancel across first to keep the products small
+        let a = gcd(self.numerator, other.denominator).max(1);
+        let b = gcd(other.numerator, self.denominator).max(1);
+        Some(Self::new(
+            (self.numerator / a).checked_mul(other.numerator / b)?,
+            (self.denominator / b).checked_mul(other.denominator / a)?,
+        ))
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self::new(n.into(), 1)
+    }
+}
+
+/// # Panics
+///
+/// On overflow; see [`Rational::checked_add`].
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).expect("fraction overflowed")
+    }
+}
+
+/// # Panics
+///
+/// On overflow; see [`Rational::checked_mul`].
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other).expect("fraction overflowed")
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pyramid() {
+        let d = Differences::new(&[1, 3, 6, 10, 15, 21]).unwrap();
+        assert_eq!(
+            d.rows(),
+            [
+                vec![1, 3, 6, 10, 15, 21],
+                vec![2, 3, 4, 5, 6],
+                vec![1, 1, 1, 1],
+                vec![0, 0, 0],
+            ]
+        );
+        assert!(d.is_settled());
+        assert_eq!(
+            d.to_string(),
+            " 1   3   6  10  15  21
+   2   3   4   5   6
+     1   1   1   1
+       0   0   0"
+        );
+
+        // Too short to reach zeros
+        let d = Differences::new(&[1, 4, 2]).unwrap();
+        assert_eq!(d.rows().last(), Some(&vec![]));
+        assert!(!d.is_settled());
+
+        assert_eq!(Differences::new(&[i64::MIN, 1]), None);
+    }
+
+    #[test]
+    fn evaluate() {
+        let p = Polynomial::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(p.degree(), Some(3));
+        assert_eq!(p.at(6), Some(68));
+        assert_eq!(p.at(-1), Some(5));
+        assert_eq!(p.at(0), Some(10));
+
+        // x² - 3x + 2 from three terms, far away in both directions
+        let p = Polynomial::fit(&[2, 0, 0]).unwrap();
+        for x in [-1000, -7, 0, 5, 1_000_000] {
+            assert_eq!(p.at(x), Some(x * x - 3 * x + 2), "{x}");
+        }
+
+        assert_eq!(Polynomial::fit(&[0, 0]).unwrap().degree(), None);
+        assert_eq!(Polynomial::fit(&[0, 0]).unwrap().at(3), Some(0));
+        assert_eq!(Polynomial::fit(&[7]).unwrap().at(-3), Some(7));
+        assert_eq!(Polynomial::fit(&[0, i64::MAX]).unwrap().at(2), None);
+    }
+
+    #[test]
+    fn coefficients() {
+        let show = |sequence: &[i64]| -> Vec<String> {
+            let p = Polynomial::fit(sequence).unwrap();
+            p.coefficients()
+                .unwrap()
+                .iter()
+                .map(|c| c.to_string())
+                .collect()
+        };
+
+        // x(x + 1) / 2 from 0
+        assert_eq!(show(&[0, 1, 3, 6, 10]), ["0", "1/2", "1/2"]);
+        // x² - 3x + 2
+        assert_eq!(show(&[2, 0, 0, 2]), ["2", "-3", "1"]);
+        assert_eq!(show(&[5, 5, 5]), ["5"]);
+        assert!(show(&[0, 0]).is_empty());
+
+        // Fitting then evaluating the coefficients agrees with `at`
+        let sequence = [3, -1, 4, 1, -5, 9];
+        let p = Polynomial::fit(&sequence).unwrap();
+        let coefficients = p.coefficients().unwrap();
+        for x in -3..10 {
+            let value = coefficients
+                .iter()
+                .rev()
+                .fold(Rational::from(0), |acc, &c| acc * Rational::from(x) + c);
+            assert!(value.is_integer());
+            assert_eq!(Some(value.numerator() as i64), p.at(x), "{x}");
+        }
+    }
+
+    #[test]
+    fn fractions() {
+        let half = Rational::new(2, 4);
+        assert_eq!((half.numerator(), half.denominator()), (1, 2));
+        assert_eq!(Rational::new(3, -6), Rational::new(-1, 2));
+        assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+        assert_eq!(half * Rational::new(4, 3), Rational::new(2, 3));
+        assert_eq!(Rational::new(0, -5), Rational::from(0));
+        assert_eq!(Rational::new(-7, 2).to_string(), "-7/2");
+    }
+}
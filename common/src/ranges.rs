@@ -0,0 +1,335 @@
+//! Sets of values stored as sorted, half-open ranges, for puzzles that move
+//! whole spans of ids around at once.
+
+use std::ops::Range;
+
+/// A set made of half-open ranges.
+///
+/// The ranges are kept sorted, non-empty and apart from each other, so
+/// `0..2` and `2..4` are stored as `0..4` and two sets with the same
+/// elements compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Ord + Copy> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort and merge `ranges` into a set.
+    fn coalesce(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+
+    /// Add `range`, merging it with any ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::coalesce(ranges);
+    }
+
+    /// The ranges in order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many separate ranges there are.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // The first range ending after `value` is the only one that can hold it
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// The smallest element.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// The end of the last range, just past the largest element.
+    pub fn end(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::coalesce(self.iter().chain(other.iter()).collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        let mut ranges = Vec::new();
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let start = x.start.max(y.start);
+            let end = x.end.min(y.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // Whichever ends first can't overlap anything further on
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        Self::coalesce(ranges)
+    }
+
+    /// The elements of `self` that aren't in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut holes = other.ranges.iter().peekable();
+
+        for range in &self.ranges {
+            let mut start = range.start;
+            while let Some(hole) = holes.peek() {
+                if hole.start >= range.end {
+                    break;
+                }
+                if hole.end > start {
+                    if hole.start > start {
+                        ranges.push(start..hole.start);
+                    }
+                    start = hole.end;
+                }
+                if hole.end > range.end {
+                    // It may cut into the next range as well
+                    break;
+                }
+                holes.next();
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self::coalesce(ranges)
+    }
+}
+
+impl<T: Ord + Copy> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::coalesce(vec![range])
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::coalesce(iter.into_iter().collect())
+    }
+}
+
+macro_rules! integer_sets {
+    ($($t:ty),*) => {$(
+        impl RangeSet<$t> {
+            /// The largest element.
+            pub fn max(&self) -> Option<$t> {
+                self.end().map(|end| end - 1)
+            }
+
+            /// How many elements there are, or `None` if that doesn't fit.
+            pub fn size(&self) -> Option<$t> {
+                self.ranges
+                    .iter()
+                    .try_fold(0 as $t, |size, r| size.checked_add(r.end - r.start))
+            }
+
+            /// Every element moved by the distance from `from` to `to`, or
+            /// `None` if one would end up out of the type's range.
+            pub fn shift(&self, from: $t, to: $t) -> Option<Self> {
+                // Wide enough that neither the distance nor the sum overflow
+                let offset = to as i128 - from as i128;
+                let moved = |x: $t| <$t>::try_from(x as i128 + offset).ok();
+
+                let ranges = self
+                    .ranges
+                    .iter()
+                    .map(|r| Some(moved(r.start)?..moved(r.end)?))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Self { ranges })
+            }
+        }
+    )*};
+}
+
+integer_sets!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every value the exhaustive tests use, with room to spare either side.
+    const DOMAIN: Range<u8> = 0..8;
+
+    /// Every set of up to two ranges within `1..7`, and the elements of each.
+    fn sets() -> Vec<(RangeSet<u8>, Vec<bool>)> {
+        let ranges: Vec<Range<u8>> = (1..7)
+            .flat_map(|start| (start..7).map(move |end| start..end))
+            .collect();
+
+        let mut sets = vec![RangeSet::new()];
+        for a in &ranges {
+            sets.push(RangeSet::from(a.clone()));
+            for b in &ranges {
+                sets.push([a.clone(), b.clone()].into_iter().collect());
+            }
+        }
+
+        sets.into_iter()
+            .map(|set| {
+                let members = DOMAIN.map(|x| set.iter().any(|r| r.contains(&x))).collect();
+                (set, members)
+            })
+            .collect()
+    }
+
+    /// The set's ranges are sorted, non-empty and apart, and hold exactly
+    /// `members`.
+    fn check(set: &RangeSet<u8>, members: impl Fn(u8) -> bool) {
+        for pair in set.ranges.windows(2) {
+            assert!(pair[0].end < pair[1].start, "{set:?} isn't coalesced");
+        }
+        assert!(set.iter().all(|r| r.start < r.end), "{set:?} has empties");
+        for x in DOMAIN {
+            assert_eq!(set.contains(x), members(x), "{x} in {set:?}");
+        }
+    }
+
+    #[test]
+    fn coalesces() {
+        let set: RangeSet<u32> = [4..6, 0..2, 2..3, 5..9, 7..7].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..3, 4..9]);
+
+        let mut set = RangeSet::new();
+        set.insert(10..20);
+        set.insert(0..5);
+        set.insert(5..10);
+        assert_eq!(set, RangeSet::from(0..20));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn queries() {
+        let set: RangeSet<i64> = [-5..-2, 3..8].into_iter().collect();
+        assert_eq!(
+            (set.min(), set.max(), set.end()),
+            (Some(-5), Some(7), Some(8))
+        );
+        assert_eq!(set.size(), Some(8));
+        assert!(set.contains(-5) && !set.contains(-2) && set.contains(7));
+
+        let empty = RangeSet::<i64>::new();
+        assert_eq!(
+            (empty.min(), empty.max(), empty.size()),
+            (None, None, Some(0))
+        );
+        assert!(!empty.contains(0));
+    }
+
+    #[test]
+    fn shifts() {
+        let set: RangeSet<u64> = [98..100, 50..52].into_iter().collect();
+        assert_eq!(
+            set.shift(98, 50).unwrap().iter().collect::<Vec<_>>(),
+            [2..4, 50..52]
+        );
+        assert_eq!(set.shift(50, 60).unwrap().min(), Some(60));
+        assert_eq!(set.shift(50, 0).unwrap().min(), Some(0));
+        assert_eq!(set.shift(51, 0), None);
+        assert_eq!(set.shift(0, u64::MAX - 99), None);
+
+        let set = RangeSet::from(-5i64..5);
+        assert_eq!(set.shift(0, -10).unwrap().min(), Some(-15));
+        assert_eq!(set.shift(i64::MIN, i64::MAX), None);
+        assert_eq!(set.shift(i64::MAX, i64::MIN), None);
+
+        let low = RangeSet::from(i64::MIN..i64::MIN + 2);
+        assert_eq!(
+            low.shift(i64::MIN, i64::MAX - 2),
+            Some(RangeSet::from(i64::MAX - 2..i64::MAX))
+        );
+    }
+
+    #[test]
+    fn union_exhaustive() {
+        for (a, in_a) in sets() {
+            for (b, in_b) in sets() {
+                let x = |x: u8| in_a[usize::from(x)] || in_b[usize::from(x)];
+                check(&a.union(&b), x);
+            }
+        }
+    }
+
+    #[test]
+    fn intersection_exhaustive() {
+        for (a, in_a) in sets() {
+            for (b, in_b) in sets() {
+                let x = |x: u8| in_a[usize::from(x)] && in_b[usize::from(x)];
+                check(&a.intersection(&b), x);
+            }
+        }
+    }
+
+    #[test]
+    fn difference_exhaustive() {
+        for (a, in_a) in sets() {
+            for (b, in_b) in sets() {
+                let x = |x: u8| in_a[usize::from(x)] && !in_b[usize::from(x)];
+                check(&a.difference(&b), x);
+            }
+        }
+    }
+
+    #[test]
+    fn laws() {
+        for (a, _) in sets() {
+            let mut inserted = RangeSet::new();
+            for range in a.iter() {
+                inserted.insert(range);
+            }
+            assert_eq!(inserted, a);
+
+            for (b, _) in sets() {
+                assert_eq!(a.union(&b), b.union(&a));
+                assert_eq!(a.intersection(&b), b.intersection(&a));
+                // Splitting `a` by `b` loses nothing and overlaps nowhere
+                let inside = a.intersection(&b);
+                let outside = a.difference(&b);
+                assert_eq!(inside.union(&outside), a);
+                assert!(inside.intersection(&outside).is_empty());
+            }
+        }
+    }
+}
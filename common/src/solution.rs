@@ -0,0 +1,40 @@
+use crate::{input, trace, Answer, ParseError, SolveError};
+
+/// A single day's puzzle.
+///
+/// `parse` turns the raw input into whatever model the day works on, and
+/// both parts are solved from that model.
+pub trait Solution {
+    const DAY: u8;
+
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+
+    fn part_1(parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+
+    fn part_2(parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+}
+
+/// Solve both parts from the input named on the command line and print the
+/// answers. This is every day binary's `main`.
+///
+/// Set `AOC_TRACE` to see the solver's diagnostics, see [`trace`].
+pub fn run<S: Solution>() {
+    trace::from_env();
+    let text = input::from_args(S::DAY);
+    let parsed = match S::parse(&text) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e.render(&text));
+            std::process::exit(1);
+        }
+    };
+
+    for (part, answer) in [(1, S::part_1(&parsed)), (2, S::part_2(&parsed))] {
+        match answer {
+            Ok(answer) => println!("Part {part}: {answer}"),
+            Err(e) => eprintln!("Part {part}: {e}"),
+        }
+    }
+}
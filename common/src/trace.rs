@@ -0,0 +1,206 @@
+//! Diagnostics from inside the solvers, silent unless asked for.
+//!
+//! Days log through the [`log`] macros, and their target is the crate they
+//! are in (`day_10`, ...). A filter names a day and optionally the most
+//! verbose level to show, like `day10` or `day8=debug`; `all` matches every
+//! day. Several filters can be separated by commas.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::{env, fmt::Display, str::FromStr};
+
+/// Filters for day binaries, which have no `--trace` option.
+pub const TRACE_VAR: &str = "AOC_TRACE";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    /// The normalized crate name, or `None` for every day's crate.
+    target: Option<String>,
+    level: LevelFilter,
+}
+
+/// Lowercase and drop `_` and `-`, so `day10`, `day-10` and `day_10` agree.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FilterError(String);
+
+impl Display for FilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bad trace filter {:?}, expected `<day>[=<level>]` or `all[=<level>]`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+impl FromStr for Filter {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || FilterError(s.to_string());
+        let (target, level) = match s.trim().split_once('=') {
+            Some((target, level)) => (target, level.parse().map_err(|_| bad())?),
+            None => (s.trim(), LevelFilter::Trace),
+        };
+
+        let target = normalize(target);
+        let target = if target == "all" {
+            None
+        } else if is_day(&target) {
+            Some(target)
+        } else {
+            return Err(bad());
+        };
+
+        Ok(Self { target, level })
+    }
+}
+
+/// Whether a normalized name is a day's, like `day10`.
+fn is_day(name: &str) -> bool {
+    name.strip_prefix("day")
+        .is_some_and(|n| n.parse::<u8>().is_ok())
+}
+
+impl Filter {
+    fn matches(&self, target: &str) -> bool {
+        // `day_1::grid` is in day 1, `day_10` is not
+        let target = normalize(target);
+        let krate = target.split("::").next().unwrap_or_default();
+        match &self.target {
+            Some(wanted) => krate == wanted,
+            // Only the days, not the libraries the runner uses
+            None => is_day(krate),
+        }
+    }
+}
+
+/// Parse a comma separated list of filters.
+pub fn filters(spec: &str) -> Result<Vec<Filter>, FilterError> {
+    spec.split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+struct Logger {
+    filters: Vec<Filter>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filters
+            .iter()
+            .any(|f| metadata.level() <= f.level && f.matches(metadata.target()))
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Send log records matching `filters` to stderr. Does nothing if there are
+/// none, or if a logger is already installed.
+pub fn init(filters: Vec<Filter>) {
+    let Some(max) = filters.iter().map(|f| f.level).max() else {
+        return;
+    };
+
+    if log::set_boxed_logger(Box::new(Logger { filters })).is_ok() {
+        log::set_max_level(max);
+    }
+}
+
+/// [`init`] from the filters in `AOC_TRACE`, if it's set. Exits if they
+/// don't parse.
+pub fn from_env() {
+    let Ok(spec) = env::var(TRACE_VAR) else {
+        return;
+    };
+
+    match filters(&spec) {
+        Ok(filters) => init(filters),
+        Err(e) => {
+            eprintln!("{TRACE_VAR}: {e}");
+            std::process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    fn enabled(spec: &str, target: &str, level: Level) -> bool {
+        let logger = Logger {
+            filters: filters(spec).unwrap(),
+        };
+        let metadata = Metadata::builder().target(target).level(level).build();
+        logger.enabled(&metadata)
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "day10".parse(),
+            Ok(Filter {
+                target: Some("day10".to_string()),
+                level: LevelFilter::Trace,
+            })
+        );
+        assert_eq!(
+            "Day-8=debug".parse(),
+            Ok(Filter {
+                target: Some("day8".to_string()),
+                level: LevelFilter::Debug,
+            })
+        );
+        assert_eq!(
+            "all=info".parse(),
+            Ok(Filter {
+                target: None,
+                level: LevelFilter::Info,
+            })
+        );
+
+        assert!("day10=loud".parse::<Filter>().is_err());
+        assert!("grid".parse::<Filter>().is_err());
+        assert_eq!(filters("day1, day2=warn,").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn targets() {
+        assert!(enabled("day10", "day_10", Level::Trace));
+        assert!(enabled("day10", "day_10::maze", Level::Debug));
+        assert!(!enabled("day1", "day_10", Level::Debug));
+        assert!(!enabled("day10", "day_8", Level::Error));
+        assert!(enabled("day1,day8", "day_8", Level::Trace));
+        assert!(enabled("all", "day_3", Level::Trace));
+        assert!(enabled("all", "day_3::grid", Level::Trace));
+        assert!(!enabled("all", "ureq", Level::Trace));
+        assert!(!enabled("all", "rustls::client", Level::Debug));
+        assert!(!enabled("all", "daybreak", Level::Trace));
+    }
+
+    #[test]
+    fn levels() {
+        assert!(enabled("day8=debug", "day_8", Level::Debug));
+        assert!(enabled("day8=debug", "day_8", Level::Warn));
+        assert!(!enabled("day8=debug", "day_8", Level::Trace));
+        assert!(!enabled("day8=off", "day_8", Level::Error));
+        assert!(!enabled("", "day_8", Level::Error));
+    }
+}
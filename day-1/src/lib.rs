@@ -0,0 +1,128 @@
+//! Day 1: Trebuchet?!
+
+use common::{Answer, ParseError, Solution, SolveError};
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(str::to_string).collect())
+}
+
+fn no_digits(line: &str) -> SolveError {
+    SolveError::BadInput(format!("no digits in {line:?}"))
+}
+
+fn overflow() -> SolveError {
+    SolveError::Overflow("sum of calibration values".to_string())
+}
+
+pub fn part_1(input: &[String]) -> Result<u32, SolveError> {
+    let mut sum: u32 = 0;
+    for line in input {
+        let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+        let first = digits.next().ok_or_else(|| no_digits(line))?;
+        let last = digits.next_back().unwrap_or(first);
+        sum = sum.checked_add((first * 10) + last).ok_or_else(overflow)?;
+    }
+
+    Ok(sum)
+}
+
+const KEYWORDS: [&str; 19] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five", "six",
+    "seven", "eight", "nine",
+];
+
+// The words start at one, so they're offset by 9 from their position.
+fn keyword_value(position: usize) -> u32 {
+    match position {
+        0..=9 => position as u32,
+        _ => position as u32 - 9,
+    }
+}
+
+pub fn part_2(input: &[String]) -> Result<u32, SolveError> {
+    let mut sum: u32 = 0;
+
+    for line in input {
+        let mut rest = line.as_str();
+        let mut first = KEYWORDS.iter().position(|first| rest.starts_with(first));
+
+        while first.is_none() && !rest.is_empty() {
+            let mut chars = rest.chars();
+            chars.next();
+            rest = chars.as_str();
+            first = KEYWORDS.iter().position(|first| rest.starts_with(first));
+        }
+
+        let first = keyword_value(first.ok_or_else(|| no_digits(line))?);
+
+        let mut last = KEYWORDS.iter().position(|last| rest.ends_with(last));
+
+        while last.is_none() && !rest.is_empty() {
+            let mut chars = rest.chars();
+            chars.next_back();
+            rest = chars.as_str();
+            last = KEYWORDS.iter().position(|last| rest.ends_with(last));
+        }
+
+        let last = keyword_value(last.ok_or_else(|| no_digits(line))?);
+
+        sum = sum.checked_add((first * 10) + last).ok_or_else(overflow)?;
+    }
+
+    Ok(sum)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::input;
+
+    #[test]
+    fn p1() {
+        let test_input = "1abc2
+            pqr3stu8vwx
+            a1b2c3d4e5f
+            treb7uchet";
+
+        assert_eq!(
+            part_1(&parse(&input::example(test_input)).unwrap()),
+            Ok(142)
+        );
+    }
+
+    #[test]
+    fn p2() {
+        let test_input = "two1nine
+        eightwothree
+        abcone2threexyz
+        xtwone3four
+        4nineeightseven2
+        zoneight234
+        7pqrstsixteen";
+
+        assert_eq!(
+            part_2(&parse(&input::example(test_input)).unwrap()),
+            Ok(281)
+        );
+    }
+}
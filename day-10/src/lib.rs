@@ -0,0 +1,355 @@
+//! Day 10: Pipe Maze
+
+use common::{
+    geometry::Direction,
+    graph::Graph,
+    grid::{Grid, Pos},
+    Answer, ParseError, Solution, SolveError,
+};
+use std::{collections::HashSet, str::FromStr};
+
+#[derive(Debug)]
+pub struct Maze {
+    map: Grid<char>,
+    start: Pos,
+}
+
+impl Maze {
+    /// Which way to leave the start so that we follow the loop.
+    fn start_direction(&self) -> Result<Direction, SolveError> {
+        [
+            (Direction::Left, "-LF"),
+            (Direction::Right, "-J7"),
+            (Direction::Up, "|7F"),
+            (Direction::Down, "|LJ"),
+        ]
+        .into_iter()
+        .find(|(direction, pipes)| {
+            self.map
+                .step(self.start, *direction)
+                .is_some_and(|next| pipes.contains(self.map[next]))
+        })
+        .map(|(direction, _)| direction)
+        .ok_or_else(|| SolveError::BadInput("the start isn't connected to any pipe".to_string()))
+    }
+
+    /// Where a pipe sends us after entering it heading in `direction`, or
+    /// `None` if it doesn't connect on that side.
+    fn turn(tile: char, direction: Direction) -> Option<Direction> {
+        use Direction::*;
+
+        match (tile, direction) {
+            ('|', Up | Down) | ('-', Left | Right) => Some(direction),
+            ('L', Down) | ('F', Up) => Some(Right),
+            ('L', Left) | ('J', Right) => Some(Up),
+            ('J', Down) | ('7', Up) => Some(Left),
+            ('7', Right) | ('F', Left) => Some(Down),
+            _ => None,
+        }
+    }
+
+    /// Every tile of the loop, starting with `S`.
+    fn get_loop(&self) -> Result<Vec<Pos>, SolveError> {
+        let mut position = self.start;
+        let mut ring = vec![position];
+        let mut direction = self.start_direction()?;
+
+        loop {
+            position = self.map.step(position, direction).ok_or_else(|| {
+                SolveError::BadInput(format!(
+                    "the loop runs off the map after line {}, column {}",
+                    position.row + 1,
+                    position.column + 1
+                ))
+            })?;
+
+            let tile = self.map[position];
+            if tile == 'S' {
+                return Ok(ring);
+            }
+            direction = Self::turn(tile, direction).ok_or_else(|| {
+                SolveError::BadInput(format!(
+                    "the loop is broken at line {}, column {}",
+                    position.row + 1,
+                    position.column + 1
+                ))
+            })?;
+
+            ring.push(position);
+        }
+    }
+
+    // Cast a ray to the right and count how many times it crosses the loop.
+    // Only pipes that connect upwards count, so that running along a `-` or
+    // touching an `F7` bend doesn't flip the parity.
+    fn is_inside_loop(&self, ring: &[Pos], point: &Pos) -> bool {
+        let above = self.start.step(Direction::Up);
+        let start_goes_up = ring
+            .get(1)
+            .into_iter()
+            .chain(ring.last())
+            .any(|&p| Some(p) == above);
+
+        ring.iter()
+            .filter(|p| p.row == point.row && p.column > point.column)
+            .filter(|&&p| match self.map[p] {
+                '|' | 'L' | 'J' => true,
+                'S' => start_goes_up,
+                _ => false,
+            })
+            .count()
+            % 2
+            == 1
+    }
+
+    fn find_voids_in_loop(&self) -> Result<usize, SolveError> {
+        let ring = self.get_loop()?;
+        let on_loop: HashSet<Pos> = ring.iter().copied().collect();
+
+        // The tiles off the loop, joined to their neighbours off the loop
+        let mut ground: Graph<Pos> = Graph::new();
+        for pos in self.map.positions().filter(|p| !on_loop.contains(p)) {
+            let id = ground.intern(pos);
+            for next in self.map.neighbors4(pos).filter(|n| !on_loop.contains(n)) {
+                let next = ground.intern(next);
+                ground.add_edge(id, next, ());
+            }
+        }
+
+        let mut voids = 0;
+        for region in ground.components() {
+            let tiles: Vec<Pos> = region.iter().map(|&id| *ground.node(id)).collect();
+            // Only the loop can fence in a region that doesn't reach the edge
+            let on_edge = |p: &Pos| {
+                p.row == 0
+                    || p.column == 0
+                    || p.row + 1 == self.map.height()
+                    || p.column + 1 == self.map.width()
+            };
+            if tiles.iter().any(on_edge) {
+                continue;
+            }
+
+            // The loop doesn't run between the tiles, so they're all on
+            // the same side of it
+            let around = tiles[0];
+            if !self.is_inside_loop(&ring, &around) {
+                continue;
+            }
+            log::debug!("{} enclosed tiles around {around}", tiles.len());
+            log::trace!("{tiles:?}");
+            voids += tiles.len();
+        }
+
+        Ok(voids)
+    }
+
+    /// The loop as a Graphviz graph of tiles, each pointing at the next.
+    pub fn to_dot(&self) -> Result<String, SolveError> {
+        let ring = self.get_loop()?;
+        let mut graph: Graph<Pos> = Graph::new();
+        for (&from, &to) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+            let (from, to) = (graph.intern(from), graph.intern(to));
+            graph.add_edge(from, to, ());
+        }
+
+        Ok(graph.to_dot("day 10", |_| None))
+    }
+}
+
+impl FromStr for Maze {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |found: &str, expected: &str| ParseError::new(Day10::DAY, s, found, expected);
+
+        let mut start = None;
+        let map = Grid::parse(Day10::DAY, s, |pos, tile| match tile {
+            "|" | "-" | "L" | "J" | "7" | "F" | "." => Ok(tile.chars().next().unwrap_or('.')),
+            "S" => match start {
+                None => {
+                    start = Some(pos);
+                    Ok('S')
+                }
+                Some(first) => {
+                    let hint = format!(
+                        "the start is already at line {}, column {}",
+                        first.row + 1,
+                        first.column + 1
+                    );
+                    Err(err(tile, "a pipe").with_hint(hint))
+                }
+            },
+            _ => {
+                let hint = "pipes are `|`, `-`, `L`, `J`, `7` and `F`, ground is `.`";
+                Err(err(tile, "a pipe, `.` or `S`").with_hint(hint))
+            }
+        })?;
+
+        let start = start.ok_or_else(|| err(&s[s.len()..], "`S`"))?;
+
+        Ok(Self { map, start })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Maze, ParseError> {
+    input.parse()
+}
+
+pub fn part_1(maze: &Maze) -> Result<usize, SolveError> {
+    Ok(maze.get_loop()?.len() / 2)
+}
+
+pub fn part_2(maze: &Maze) -> Result<usize, SolveError> {
+    maze.find_voids_in_loop()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Parsed = Maze;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+
+    const INPUT_2: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const INPUT_3: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    const INPUT_4: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    #[test]
+    fn test_parse() {
+        dbg!(INPUT.parse::<Maze>().unwrap());
+    }
+
+    #[test]
+    fn test_loop() {
+        dbg!(INPUT.parse::<Maze>().unwrap().get_loop().unwrap());
+    }
+
+    #[test]
+    fn test_inside_loop() {
+        let maze = INPUT.parse::<Maze>().unwrap();
+        let ring = maze.get_loop().unwrap();
+
+        assert!(maze.is_inside_loop(&ring, &Pos::new(2, 2)));
+        // Running along the top of the loop isn't crossing it
+        assert!(!maze.is_inside_loop(&ring, &Pos::new(1, 0)));
+        assert!(!maze.is_inside_loop(&ring, &Pos::new(2, 4)));
+    }
+
+    // Assumption: there's only 1 answer
+    #[test]
+    fn p1() {
+        assert_eq!(part_1(&parse(INPUT).unwrap()), Ok(4));
+    }
+
+    #[test]
+    fn p2() {
+        assert_eq!(part_2(&parse(INPUT_2).unwrap()), Ok(4));
+        assert_eq!(part_2(&parse(INPUT_4).unwrap()), Ok(8));
+        assert_eq!(part_2(&parse(INPUT_3).unwrap()), Ok(10));
+    }
+
+    #[test]
+    fn dot() {
+        let dot = parse(INPUT).unwrap().to_dot().unwrap();
+        assert!(dot.contains("    n0 [label=\"(1, 1)\"];\n    n1 [label=\"(1, 2)\"];"));
+        assert!(dot.ends_with("    n7 -> n0;\n}\n"));
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse(".....\n.S-7.\n.|.|.\n.L-X.\n.....").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 4));
+        assert_eq!(err.found, "X");
+
+        let err = parse(".....\n.|-7.").unwrap_err();
+        assert_eq!(err.expected, "`S`");
+    }
+
+    #[test]
+    fn solve_error() {
+        let maze = parse(".....\n.S-7.\n.|.|.\n.L-|.\n.....").unwrap();
+        assert_eq!(
+            part_1(&maze),
+            Err(SolveError::BadInput(
+                "the loop is broken at line 5, column 4".to_string()
+            ))
+        );
+
+        let maze = parse("...\n.S.\n...").unwrap();
+        assert!(matches!(part_2(&maze), Err(SolveError::BadInput(_))));
+    }
+
+    #[test]
+    fn diagnostic() {
+        let input = ".....\n.S-7.\n.|.|.\n.L-X.\n.....";
+
+        assert_eq!(
+            parse(input).unwrap_err().render(input),
+            r#"error: expected a pipe, `.` or `S`, found "X"
+ --> day 10, line 4, column 4
+  |
+4 | .L-X.
+  |    ^
+  |
+  = hint: pipes are `|`, `-`, `L`, `J`, `7` and `F`, ground is `.`"#
+        );
+
+        let input = ".S-7.\n.|.S.";
+        assert!(parse(input)
+            .unwrap_err()
+            .render(input)
+            .ends_with("= hint: the start is already at line 1, column 2"));
+    }
+}
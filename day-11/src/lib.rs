@@ -0,0 +1,149 @@
+//! Day 11: Cosmic Expansion
+
+use common::{
+    grid::{Grid, Pos},
+    Answer, ParseError, Solution, SolveError,
+};
+use itertools::Itertools;
+
+/// The empty rows and columns, which expand.
+fn get_expansions(pixels: &Grid<bool>) -> (Vec<usize>, Vec<usize>) {
+    let rows = pixels
+        .rows()
+        .enumerate()
+        .filter_map(|(i, row)| (!row.contains(&true)).then_some(i))
+        .collect();
+    let columns = pixels
+        .columns()
+        .enumerate()
+        .filter_map(|(j, mut column)| (!column.any(|&galaxy| galaxy)).then_some(j))
+        .collect();
+
+    (rows, columns)
+}
+
+/// The galaxies as observed, before accounting for the expansion of the
+/// empty rows and columns.
+#[derive(Debug)]
+pub struct Image {
+    galaxies: Vec<Pos>,
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+}
+
+pub fn parse(input: &str) -> Result<Image, ParseError> {
+    let pixels = Grid::parse(Day11::DAY, input, |_, c| match c {
+        "#" => Ok(true),
+        "." => Ok(false),
+        _ => Err(ParseError::new(Day11::DAY, input, c, "`.` or `#`")),
+    })?;
+
+    let galaxies = pixels
+        .iter()
+        .filter_map(|(pos, &galaxy)| galaxy.then_some(pos))
+        .collect();
+    let (rows, columns) = get_expansions(&pixels);
+
+    Ok(Image {
+        galaxies,
+        rows,
+        columns,
+    })
+}
+
+/// Where the galaxies end up, or `None` if the universe expands past
+/// `usize::MAX`.
+fn galaxies(image: &Image, ex: usize) -> Option<Vec<Pos>> {
+    image
+        .galaxies
+        .iter()
+        .map(|&Pos { row: i, column: j }| {
+            let r_ex = image
+                .rows
+                .iter()
+                .filter(|n| n < &&i)
+                .count()
+                .checked_mul(ex)?;
+            let c_ex = image
+                .columns
+                .iter()
+                .filter(|n| n < &&j)
+                .count()
+                .checked_mul(ex)?;
+            Some(Pos::new(i.checked_add(r_ex)?, j.checked_add(c_ex)?))
+        })
+        .collect()
+}
+
+fn distance_sum(image: &Image, expansion_factor: usize) -> Result<usize, SolveError> {
+    let overflow = || SolveError::Overflow(format!("distances at expansion {expansion_factor}"));
+
+    galaxies(image, expansion_factor - 1)
+        .ok_or_else(overflow)?
+        .iter()
+        .combinations(2)
+        .map(|pair| pair[0].manhattan(*pair[1]))
+        .try_fold(0usize, |sum, d| sum.checked_add(d))
+        .ok_or_else(overflow)
+}
+
+pub fn part_1(image: &Image) -> Result<usize, SolveError> {
+    distance_sum(image, 2)
+}
+
+pub fn part_2(image: &Image) -> Result<usize, SolveError> {
+    distance_sum(image, 1_000_000)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Parsed = Image;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn test_find() {
+        dbg!(galaxies(&parse(INPUT).unwrap(), 1));
+    }
+
+    #[test]
+    fn p1() {
+        assert_eq!(part_1(&parse(INPUT).unwrap()), Ok(374));
+    }
+
+    #[test]
+    fn p2() {
+        let image = parse(INPUT).unwrap();
+        assert_eq!(distance_sum(&image, 10), Ok(1030));
+        assert_eq!(distance_sum(&image, 100), Ok(8410));
+    }
+}
@@ -0,0 +1,175 @@
+//! Day 2: Cube Conundrum
+
+use common::{
+    parse_lines,
+    parser::{
+        self, delimited, integer, label, literal, map, one_of, pair, separated, spaced, Parser,
+    },
+    Answer, ParseError, Solution, SolveError,
+};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Bag {
+    idx: u32,
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl Bag {
+    fn can_hold(&self, r: u32, g: u32, b: u32) -> bool {
+        self.red <= r && self.green <= g && self.blue <= b
+    }
+
+    fn power(&self) -> Option<u32> {
+        self.red.checked_mul(self.blue)?.checked_mul(self.green)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Colour {
+    Red,
+    Green,
+    Blue,
+}
+
+/// `Game <id>: ` and the games, each a `,`-separated list of cubes, with
+/// `;` between them.
+fn bag<'a>() -> impl Parser<'a, Bag> {
+    let colour = one_of([
+        ("red", Colour::Red),
+        ("green", Colour::Green),
+        ("blue", Colour::Blue),
+    ]);
+    let cube = label(pair(integer::<u32>(), spaced(colour)), "`<count> <colour>`");
+    let game = separated(spaced(cube), literal(","));
+    let idx = delimited(literal("Game "), integer(), literal(":"));
+
+    map(pair(idx, separated(game, literal(";"))), |(idx, games)| {
+        let mut bag = Bag {
+            idx,
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+        for (n, colour) in games.into_iter().flatten() {
+            let most = match colour {
+                Colour::Red => &mut bag.red,
+                Colour::Green => &mut bag.green,
+                Colour::Blue => &mut bag.blue,
+            };
+            *most = (*most).max(n);
+        }
+        bag
+    })
+}
+
+impl FromStr for Bag {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse(Day2::DAY, s, bag())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Bag>, ParseError> {
+    parse_lines(input)
+}
+
+pub fn part_1(input: &[Bag]) -> Result<u32, SolveError> {
+    input
+        .iter()
+        .filter(|b| b.can_hold(12, 13, 14))
+        .try_fold(0u32, |acc, b| acc.checked_add(b.idx))
+        .ok_or_else(|| SolveError::Overflow("sum of game IDs".to_string()))
+}
+
+pub fn part_2(input: &[Bag]) -> Result<u32, SolveError> {
+    input
+        .iter()
+        .try_fold(0u32, |acc, b| acc.checked_add(b.power()?))
+        .ok_or_else(|| SolveError::Overflow("sum of powers".to_string()))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Parsed = Vec<Bag>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn p1() {
+        let test_input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+        assert_eq!(part_1(&parse(test_input).unwrap()), Ok(8));
+    }
+
+    #[test]
+    fn p2() {
+        let test_input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+        assert_eq!(part_2(&parse(test_input).unwrap()), Ok(2286));
+    }
+
+    #[test]
+    fn overflow() {
+        let ids = parse("Game 4000000000: 1 red\nGame 4000000000: 1 blue").unwrap();
+        assert!(matches!(part_1(&ids), Err(SolveError::Overflow(_))));
+
+        let cubes = parse("Game 1: 70000 red, 70000 blue, 1 green").unwrap();
+        assert!(matches!(part_2(&cubes), Err(SolveError::Overflow(_))));
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse("Game 1: 3 blue, 4 red\nGame 7:").unwrap_err();
+        assert_eq!((err.day, err.line, err.column), (2, 2, 8));
+        assert_eq!(err.expected, "`<count> <colour>`");
+
+        let err = parse("Game 1: 3 blue, 4 purple").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (19, "purple"));
+    }
+
+    #[test]
+    fn diagnostic() {
+        let input = "Game 6: 1 red, 2 blue\nGame 7:";
+
+        assert_eq!(
+            parse(input).unwrap_err().render(input),
+            "error: expected `<count> <colour>`, found end of line
+ --> day 2, line 2, column 8
+  |
+2 | Game 7:
+  |        ^
+  |
+  = hint: the line ends early, is the input truncated?"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Day 3: Gear Ratios
+
+use common::{
+    grid::{Grid, Pos},
+    numbers::{integers, Number},
+    Answer, ParseError, Solution, SolveError,
+};
+use std::{collections::HashMap, str::FromStr};
+
+/// Anything but a digit or a `.`.
+fn is_symbol(c: char) -> bool {
+    !c.is_numeric() && c != '.'
+}
+
+#[derive(Debug, Clone)]
+struct Part {
+    value: u32,
+    indicies: Vec<Pos>,
+}
+
+impl Part {
+    /// The cells around the number, some more than once.
+    fn neighbors<'a>(&'a self, grid: &'a Grid<char>) -> impl Iterator<Item = Pos> + 'a {
+        self.indicies.iter().flat_map(|&idx| grid.neighbors8(idx))
+    }
+
+    fn part_value(&self, grid: &Grid<char>) -> u32 {
+        if self.neighbors(grid).any(|xy| is_symbol(grid[xy])) {
+            self.value
+        } else {
+            0
+        }
+    }
+
+    fn adjacent_gears(&self, grid: &Grid<char>, gears: &mut HashMap<Pos, Vec<u32>>) {
+        if let Some(xy) = self.neighbors(grid).find(|&xy| grid[xy] == '*') {
+            gears.entry(xy).or_default().push(self.value);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    grid: Grid<char>,
+    parts: Vec<Part>,
+}
+
+impl FromStr for Schematic {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            // Symbols, `-` included, are never part of a number
+            for Number { value, span } in integers::<u32>(Day3::DAY, s, line)? {
+                let indicies = span.map(|j| Pos::new(i, j)).collect();
+                parts.push(Part { value, indicies });
+            }
+        }
+
+        let grid = Grid::parse(Day3::DAY, s, |_, c| Ok(c.chars().next().unwrap_or('.')))?;
+
+        Ok(Self { grid, parts })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Schematic, ParseError> {
+    input.parse()
+}
+
+pub fn part_1(schem: &Schematic) -> Result<u32, SolveError> {
+    schem
+        .parts
+        .iter()
+        .try_fold(0u32, |acc, v| acc.checked_add(v.part_value(&schem.grid)))
+        .ok_or_else(|| SolveError::Overflow("sum of part numbers".to_string()))
+}
+
+pub fn part_2(schem: &Schematic) -> Result<u32, SolveError> {
+    let overflow = || SolveError::Overflow("sum of gear ratios".to_string());
+    let mut sum: u32 = 0;
+    let mut gears = HashMap::new();
+    for v in &schem.parts {
+        v.adjacent_gears(&schem.grid, &mut gears);
+    }
+
+    for (_, v) in gears {
+        if v.len() == 2 {
+            let ratio = v[0].checked_mul(v[1]).ok_or_else(overflow)?;
+            sum = sum.checked_add(ratio).ok_or_else(overflow)?;
+        }
+    }
+
+    Ok(sum)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Parsed = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$/*....
+.664.598..";
+        for line in input.lines() {
+            println!("{:?}", integers::<u32>(Day3::DAY, line, line).unwrap());
+        }
+    }
+
+    #[test]
+    fn p1() {
+        let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+        assert_eq!(part_1(&parse(input).unwrap()), Ok(4361));
+    }
+
+    #[test]
+    fn p2() {
+        let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        assert_eq!(part_2(&parse(input).unwrap()), Ok(467835));
+    }
+
+    #[test]
+    fn solve_error() {
+        let schem = parse("70000*70000").unwrap();
+        assert!(matches!(part_2(&schem), Err(SolveError::Overflow(_))));
+
+        let schem = parse("4000000000*4000000000").unwrap();
+        assert!(matches!(part_1(&schem), Err(SolveError::Overflow(_))));
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse("467..114..\n...*99999999999").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.found, "99999999999");
+    }
+}
@@ -0,0 +1,160 @@
+//! Day 4: Scratchcards
+
+use common::{
+    parse_lines,
+    parser::{self, delimited, integer, list, literal, map, pair, preceded, spaces, Parser},
+    Answer, ParseError, Solution, SolveError,
+};
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    winners: Vec<u32>,
+    recieved: Vec<u32>,
+}
+
+impl Card {
+    fn matches(&self) -> usize {
+        self.recieved
+            .iter()
+            .filter(|n| self.winners.contains(n))
+            .count()
+    }
+
+    /// The card's points, or `None` if they don't fit in a `u32`.
+    fn score(&self) -> Option<u32> {
+        let matches = self
+            .recieved
+            .iter()
+            .filter(|n| self.winners.contains(n))
+            .count();
+
+        match matches {
+            0 => Some(0),
+            n => 1u32.checked_shl(n as u32 - 1),
+        }
+    }
+}
+
+/// `Card <id>:`, the winning numbers, `|` and the numbers on the card.
+fn card<'a>() -> impl Parser<'a, Card> {
+    let id = delimited(
+        pair(literal("Card"), spaces()),
+        integer::<u32>(),
+        literal(":"),
+    );
+    let winners = preceded(id, list(integer()));
+    let recieved = preceded(literal("|"), list(integer()));
+
+    map(pair(winners, recieved), |(winners, recieved)| Card {
+        winners,
+        recieved,
+    })
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse(Day4::DAY, s, card())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_lines(input)
+}
+
+pub fn part_1(cards: &[Card]) -> Result<u32, SolveError> {
+    cards
+        .iter()
+        .try_fold(0u32, |acc, card| acc.checked_add(card.score()?))
+        .ok_or_else(|| SolveError::Overflow("total points".to_string()))
+}
+
+pub fn part_2(cards: &[Card]) -> Result<u32, SolveError> {
+    let overflow = || SolveError::Overflow("number of scratchcards".to_string());
+    let mut counts: Vec<u32> = Vec::new();
+    counts.resize(cards.len(), 1);
+
+    let mut sum: u32 = 0;
+    for (i, card) in cards.iter().enumerate() {
+        let copies = counts[i];
+        sum = sum.checked_add(copies).ok_or_else(overflow)?;
+
+        let matches = card.matches();
+        let won = counts.get_mut(i + 1..i + 1 + matches).ok_or_else(|| {
+            SolveError::BadInput(format!("card {} wins cards past the end", i + 1))
+        })?;
+        for c in won {
+            *c = c.checked_add(copies).ok_or_else(overflow)?;
+        }
+    }
+
+    Ok(sum)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Parsed = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shifting() {
+        let x: u32 = 2;
+
+        dbg!(x << 1);
+    }
+
+    #[test]
+    fn p1() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+        assert_eq!(part_1(&parse(input).unwrap()), Ok(13));
+    }
+
+    #[test]
+    fn p2() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+        assert_eq!(part_2(&parse(input).unwrap()), Ok(30));
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse("Card 1: 41 48 | 83 86\nCard 2: 13 32 | 6l 30").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 17));
+        assert_eq!(err.found, "6l");
+
+        let err = parse("Card 1: 41 48 83 86").unwrap_err();
+        assert_eq!(err.expected, "`|`");
+    }
+}
@@ -0,0 +1,295 @@
+//! Day 5: If You Give A Seed A Fertilizer
+
+use common::{
+    parser::{
+        integer, label, list, literal, parse_within, preceded, split_sections, take_while,
+        terminated,
+    },
+    ranges::RangeSet,
+    Answer, ParseError, Solution, SolveError,
+};
+use std::str::FromStr;
+
+fn convert_seed(destination: u64, source: u64, delta: u64, input: u64) -> Option<u64> {
+    match input.checked_sub(source) {
+        Some(x) => (x < delta).then_some(destination + (input - source)),
+        None => None,
+    }
+}
+
+/// Split `ids` into the ones the mapping leaves alone and where it sends
+/// the rest.
+///
+/// The parser guarantees `source + delta` and `destination + delta` fit in a
+/// `u64`, so none of this can overflow.
+fn convert_seed_range(
+    destination: u64,
+    source: u64,
+    delta: u64,
+    ids: &RangeSet<u64>,
+) -> (RangeSet<u64>, RangeSet<u64>) {
+    let mapped = RangeSet::from(source..source + delta);
+    let changed = ids
+        .intersection(&mapped)
+        .shift(source, destination)
+        .expect("mappings fit in 64 bits");
+
+    (ids.difference(&mapped), changed)
+}
+
+#[derive(Debug)]
+pub struct Maps {
+    seeds: Vec<u64>,
+    conversions: Vec<Vec<Vec<u64>>>,
+}
+
+impl FromStr for Maps {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |found: &str, expected: &str| ParseError::new(Day5::DAY, s, found, expected);
+        let mut sections = split_sections(s).into_iter();
+
+        let Some(first) = sections.next() else {
+            return Err(err(&s[s.len()..], "`seeds: `"));
+        };
+        let seeds = parse_within(
+            Day5::DAY,
+            s,
+            first.header,
+            preceded(literal("seeds: "), list(integer())),
+        )?;
+        if let Some(line) = first.body.first() {
+            return Err(err(line, "a blank line"));
+        }
+
+        // Each map starts from where the one before it ends up
+        let mut from = "seed";
+        let mut conversions = Vec::new();
+        for section in sections {
+            let name = terminated(take_while(|c| c != ' '), literal(" map:"));
+            let name = label(name, "`<source>-to-<destination> map:`");
+            let name = parse_within(Day5::DAY, s, section.header, name)?;
+            match name.split_once("-to-") {
+                Some((source, destination)) if source == from => from = destination,
+                _ => {
+                    let hint = format!("the map before leads to `{from}`");
+                    return Err(
+                        err(section.header, &format!("`{from}-to-<destination> map:`"))
+                            .with_hint(hint),
+                    );
+                }
+            }
+
+            let mut section_vec: Vec<Vec<u64>> = Vec::new();
+            for line in section.body {
+                let mapping: Vec<u64> = parse_within(Day5::DAY, s, line, list(integer()))?;
+                if mapping.len() != 3 {
+                    let hint = format!("found {} numbers instead of 3", mapping.len());
+                    return Err(err(line, "`<destination> <source> <length>`").with_hint(hint));
+                }
+                if mapping[0].max(mapping[1]).checked_add(mapping[2]).is_none() {
+                    return Err(err(line, "a range that fits in 64 bits"));
+                }
+                section_vec.push(mapping);
+            }
+            conversions.push(section_vec);
+        }
+
+        Ok(Self { seeds, conversions })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Maps, ParseError> {
+    input.parse()
+}
+
+pub fn part_1(maps: &Maps) -> Result<u64, SolveError> {
+    let mut result = None;
+
+    for mut seed in maps.seeds.iter().copied() {
+        for con in &maps.conversions {
+            for mapping in con {
+                if let Some(x) = convert_seed(mapping[0], mapping[1], mapping[2], seed) {
+                    seed = x;
+                    break;
+                }
+            }
+        }
+        result = Some(result.map_or(seed, |r: u64| r.min(seed)));
+    }
+
+    result.ok_or_else(|| SolveError::NoSolution("there are no seeds".to_string()))
+}
+
+pub fn part_2(maps: &Maps) -> Result<u64, SolveError> {
+    if !maps.seeds.len().is_multiple_of(2) {
+        return Err(SolveError::BadInput(
+            "seeds must come in `<start> <length>` pairs".to_string(),
+        ));
+    }
+
+    let mut locations: RangeSet<u64> = maps
+        .seeds
+        .chunks_exact(2)
+        .map(|c| {
+            c[0].checked_add(c[1])
+                .map(|end| c[0]..end)
+                .ok_or_else(|| SolveError::Overflow(format!("seed range starting at {}", c[0])))
+        })
+        .collect::<Result<_, _>>()?;
+
+    for mappings in &maps.conversions {
+        // Each id is moved by the first mapping that covers it, if any
+        let mut unchanged = locations;
+        let mut changed = RangeSet::new();
+        for mapping in mappings {
+            let (rest, moved) = convert_seed_range(mapping[0], mapping[1], mapping[2], &unchanged);
+            unchanged = rest;
+            changed = changed.union(&moved);
+        }
+        locations = changed.union(&unchanged);
+    }
+
+    locations
+        .min()
+        .ok_or_else(|| SolveError::NoSolution("there are no seeds".to_string()))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Parsed = Maps;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::input;
+
+    const INPUT: &str = "seeds: 79 14 55 13\r
+\r
+seed-to-soil map:
+50 98 2
+52 50 48\r
+\r
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15\r
+\r
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4\r
+\r
+water-to-light map:
+88 18 7
+18 25 70\r
+\r
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13\r
+\r
+temperature-to-humidity map:
+0 69 1
+1 0 69\r
+\r
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_conversion() {
+        assert_eq!(convert_seed(50, 98, 2, 97), None);
+        assert_eq!(convert_seed(50, 98, 2, 98), Some(50));
+        assert_eq!(convert_seed(50, 98, 2, 99), Some(51));
+        assert_eq!(convert_seed(50, 98, 2, 100), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        dbg!(input::example(INPUT).parse::<Maps>().unwrap());
+    }
+
+    #[test]
+    fn test_part_2_parse() {
+        //dbg!(part2map(input).unwrap());
+    }
+
+    #[test]
+    fn test_range_comparisons() {
+        let convert = |start, end| convert_seed_range(50, 98, 2, &RangeSet::from(start..end));
+        let none = RangeSet::new();
+
+        assert_eq!(convert(90, 95), (RangeSet::from(90..95), none.clone()));
+        assert_eq!(convert(98, 100), (none, RangeSet::from(50..52)));
+        assert_eq!(
+            convert(90, 99),
+            (RangeSet::from(90..98), RangeSet::from(50..51))
+        );
+        assert_eq!(
+            convert(90, 110),
+            (
+                [90..98, 100..110].into_iter().collect(),
+                RangeSet::from(50..52)
+            )
+        );
+    }
+
+    #[test]
+    fn p1() {
+        assert_eq!(part_1(&parse(&input::example(INPUT)).unwrap()), Ok(35))
+    }
+
+    #[test]
+    fn line_endings() {
+        // The sections are found the same way without normalizing first
+        assert_eq!(part_1(&parse(INPUT).unwrap()), Ok(35))
+    }
+
+    #[test]
+    fn p2() {
+        assert_eq!(part_2(&parse(&input::example(INPUT)).unwrap()), Ok(46))
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 1));
+        assert_eq!(err.found, "52 50");
+
+        let err = parse("seed: 79 14").unwrap_err();
+        assert_eq!(err.expected, "`seeds: `");
+
+        let err =
+            parse("seeds: 79\n\nseed-to-soil map:\n50 98 2\n\nwater-to-light map:").unwrap_err();
+        assert_eq!((err.line, err.found.as_str()), (6, "water-to-light map:"));
+        assert_eq!(err.expected, "`soil-to-<destination> map:`");
+    }
+
+    #[test]
+    fn solve_error() {
+        let maps = parse("seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2").unwrap();
+        assert!(matches!(part_2(&maps), Err(SolveError::BadInput(_))));
+
+        let maps = parse("seeds: \n\nseed-to-soil map:\n50 98 2").unwrap();
+        assert!(matches!(part_1(&maps), Err(SolveError::NoSolution(_))));
+    }
+}
@@ -0,0 +1,176 @@
+//! Day 6: Wait For It
+
+use common::{
+    parser::{
+        self, integer, key_value, line_end, list, literal, map, pair, spanned, take_while,
+        terminated, try_map, Parser,
+    },
+    Answer, ParseError, Solution, SolveError,
+};
+
+fn quadratic(a: f64, b: f64, c: f64) -> (f64, f64) {
+    let s = (b * b - 4. * a * c).sqrt();
+    ((-b + s) / (2. * a), (-b - s) / (2. * a))
+}
+
+#[derive(Debug)]
+pub struct Race {
+    time: usize,
+    record: usize,
+}
+
+impl Race {
+    fn ways_to_win(&self) -> usize {
+        let limits = quadratic(-1., self.time as f64, -(self.record as f64));
+
+        // Strictly between the roots: matching the record doesn't count.
+        // An unbeatable record has no real roots, and NaN casts to 0.
+        let lower = (limits.0.floor() as usize).saturating_add(1);
+        let upper = (limits.1.ceil() as usize).saturating_sub(1);
+
+        // The +1 is to be inclusive of both ends
+        upper.saturating_add(1).saturating_sub(lower)
+    }
+}
+
+/// The `Time:` and `Distance:` rows, each read by a parser from `value`.
+fn rows<'a, T, P: Parser<'a, T>>(s: &'a str, value: impl Fn() -> P) -> Result<(T, T), ParseError> {
+    let row = |label| map(key_value(literal(label), ":", value()), |(_, v)| v);
+    parser::parse(
+        Day6::DAY,
+        s,
+        pair(terminated(row("Time"), line_end()), row("Distance")),
+    )
+}
+
+fn read_races(s: &str) -> Result<Vec<Race>, ParseError> {
+    let ((times, _), (records, distances)) = rows(s, || spanned(list(integer::<usize>())))?;
+
+    if records.len() != times.len() {
+        let expected = format!("a record for each of the {} races", times.len());
+        return Err(ParseError::new(Day6::DAY, s, distances, expected));
+    }
+
+    Ok(times
+        .iter()
+        .zip(records)
+        .map(|(time, record)| Race {
+            time: *time,
+            record,
+        })
+        .collect())
+}
+
+fn read_frag_race(s: &str) -> Result<Race, ParseError> {
+    // The spaces between the digits don't count
+    let number = || {
+        try_map(take_while(|c| c != '\n'), |text: &str| {
+            let digits: String = text.split_whitespace().collect();
+            digits.parse().map_err(|_| "a number".to_string())
+        })
+    };
+
+    let (time, record) = rows(s, number)?;
+
+    Ok(Race { time, record })
+}
+
+/// The sheet of paper, read both as separate races and as one long race
+/// with bad kerning.
+#[derive(Debug)]
+pub struct Sheet {
+    races: Vec<Race>,
+    race: Race,
+}
+
+pub fn parse(input: &str) -> Result<Sheet, ParseError> {
+    Ok(Sheet {
+        races: read_races(input)?,
+        race: read_frag_race(input)?,
+    })
+}
+
+pub fn part_1(sheet: &Sheet) -> Result<usize, SolveError> {
+    let mut mul: usize = 1;
+    for race in &sheet.races {
+        mul = mul
+            .checked_mul(race.ways_to_win())
+            .ok_or_else(|| SolveError::Overflow("product of ways to win".to_string()))?;
+    }
+
+    Ok(mul)
+}
+
+pub fn part_2(sheet: &Sheet) -> Result<usize, SolveError> {
+    Ok(sheet.race.ways_to_win())
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Parsed = Sheet;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn check_parse() {
+        dbg!(read_races(INPUT).unwrap());
+    }
+
+    #[test]
+    fn check_parse_2() {
+        dbg!(read_frag_race(INPUT).unwrap());
+    }
+
+    #[test]
+    fn check_wins() {
+        let races = read_races(INPUT).unwrap();
+        dbg!(races[0].ways_to_win());
+
+        let unbeatable = Race {
+            time: 3,
+            record: 100,
+        };
+        assert_eq!(unbeatable.ways_to_win(), 0);
+    }
+
+    #[test]
+    fn p1() {
+        assert_eq!(part_1(&parse(INPUT).unwrap()), Ok(288));
+    }
+
+    #[test]
+    fn p2() {
+        assert_eq!(part_2(&parse(INPUT).unwrap()), Ok(71503));
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse("Time: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 11));
+        assert_eq!(err.expected, "a record for each of the 2 races");
+
+        let err = parse("Time: 7 1x\nDistance: 9 40").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (9, "1x"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Day 7: Camel Cards
+
+use common::{parse_lines, parse_number, Answer, ParseError, Solution, SolveError};
+use std::{collections::HashMap, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Hand {
+    strength: u8,
+    cards: [u8; 5],
+    bid: usize,
+}
+
+// Jokers are stored as 1 so they sort below every other card.
+const JOKER: u8 = 1;
+
+fn strength(cards: &[u8; 5]) -> u8 {
+    let mut strength_hash = HashMap::new();
+    let mut j_count = 0;
+    cards.iter().for_each(|c| {
+        if *c == JOKER {
+            j_count += 1;
+        } else if strength_hash.contains_key(c) {
+            strength_hash.insert(c, strength_hash[c] + 1);
+        } else {
+            strength_hash.insert(c, 1);
+        }
+    });
+
+    for key in strength_hash.clone().keys() {
+        strength_hash.insert(*key, strength_hash[key] + j_count);
+    }
+
+    if strength_hash.len() <= 1 {
+        7
+    } else if strength_hash.len() == 2 && strength_hash.values().any(|v| *v == 4) {
+        6
+    } else if strength_hash.len() == 2 {
+        5
+    } else if strength_hash.len() == 3 && strength_hash.values().any(|v| *v == 3) {
+        4
+    } else if strength_hash.len() == 3 {
+        3
+    } else if strength_hash.len() == 4 {
+        2
+    } else {
+        1
+    }
+}
+
+impl Hand {
+    fn with_jokers(mut self) -> Self {
+        self.cards
+            .iter_mut()
+            .filter(|c| **c == 11)
+            .for_each(|c| *c = JOKER);
+        self.strength = strength(&self.cards);
+
+        self
+    }
+}
+
+impl FromStr for Hand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |found: &str, expected: &str| ParseError::new(Day7::DAY, s, found, expected);
+
+        let (hand, bid) = s
+            .split_once(' ')
+            .ok_or_else(|| err(&s[s.len()..], "a bid"))?;
+
+        let bid = parse_number(Day7::DAY, s, bid)?;
+
+        let cards: [u8; 5] = hand
+            .char_indices()
+            .map(|(i, c)| match c {
+                'A' => Ok(14),
+                'K' => Ok(13),
+                'Q' => Ok(12),
+                'J' => Ok(11),
+                'T' => Ok(10),
+                '2'..='9' => Ok(c as u8 - b'0'),
+                _ => Err(err(&hand[i..i + c.len_utf8()], "a card")
+                    .with_hint("cards are `A`, `K`, `Q`, `J`, `T` and `2` to `9`")),
+            })
+            .collect::<Result<Vec<u8>, _>>()?
+            .try_into()
+            .map_err(|_| err(hand, "five cards"))?;
+
+        Ok(Hand {
+            strength: strength(&cards),
+            cards,
+            bid,
+        })
+    }
+}
+
+fn winnings(mut hands: Vec<Hand>) -> Result<usize, SolveError> {
+    hands.sort();
+
+    hands
+        .iter()
+        .enumerate()
+        .try_fold(0usize, |acc, (i, hand)| {
+            hand.bid.checked_mul(i + 1)?.checked_add(acc)
+        })
+        .ok_or_else(|| SolveError::Overflow("total winnings".to_string()))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Hand>, ParseError> {
+    parse_lines(input)
+}
+
+pub fn part_1(hands: &[Hand]) -> Result<usize, SolveError> {
+    winnings(hands.to_vec())
+}
+
+pub fn part_2(hands: &[Hand]) -> Result<usize, SolveError> {
+    winnings(hands.iter().cloned().map(Hand::with_jokers).collect())
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Parsed = Vec<Hand>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_parse() {
+        for line in INPUT.lines() {
+            dbg!(line.parse::<Hand>().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut hands: Vec<Hand> = Vec::new();
+
+        for line in INPUT.lines() {
+            hands.push(line.parse().unwrap());
+        }
+        hands.sort();
+        dbg!(hands);
+    }
+
+    #[test]
+    fn test_jokers() {
+        // J is a jack in part 1 and only becomes a joker in part 2
+        let hand = "KTJJT 220".parse::<Hand>().unwrap();
+        assert_eq!((hand.strength, hand.cards[2]), (3, 11));
+
+        let hand = hand.with_jokers();
+        assert_eq!((hand.strength, hand.cards[2]), (6, JOKER));
+    }
+
+    #[test]
+    fn p1() {
+        assert_eq!(part_1(&parse(INPUT).unwrap()), Ok(6440));
+    }
+
+    #[test]
+    fn p2() {
+        assert_eq!(part_2(&parse(INPUT).unwrap()), Ok(5905));
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse("32T3K 765\nT551J 684").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.found, "1");
+
+        let err = parse("32T3 765").unwrap_err();
+        assert_eq!(err.expected, "five cards");
+    }
+}
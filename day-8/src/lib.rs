@@ -0,0 +1,342 @@
+//! Day 8: Haunted Wasteland
+
+use common::{
+    graph::{Graph, NodeId},
+    math::{first_alignment, Cycle},
+    parser::{
+        delimited, key_value, label, line_end, literal, many1, one_of, pair, parse_within, spaced,
+        split_sections, terminated, word,
+    },
+    Answer, ParseError, Solution, SolveError,
+};
+use std::{collections::HashMap, str::FromStr};
+
+#[derive(Debug, Clone, Copy)]
+enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    /// Which of a node's edges this follows; the left one is added first.
+    fn edge(self) -> usize {
+        match self {
+            Turn::Left => 0,
+            Turn::Right => 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Map {
+    directions: Vec<Turn>,
+    network: Graph<String, Turn>,
+    distances: HashMap<String, Cycle>,
+}
+
+impl Map {
+    fn take(&self, node: NodeId, turn: Turn) -> NodeId {
+        self.network.edges(node)[turn.edge()].0
+    }
+
+    fn steps(&self, start: &str, end: &str) -> Result<u32, SolveError> {
+        let Some(start) = self.network.id(start) else {
+            return Err(SolveError::NoSolution(format!(
+                "there is no `{start}` node"
+            )));
+        };
+        let unreachable = || SolveError::NoSolution(format!("`{end}` is never reached"));
+        let end = self.network.id(end).ok_or_else(unreachable)?;
+        if self.network.bfs(start)[end].is_none() {
+            return Err(unreachable());
+        }
+
+        // Past this many steps we're going round in circles
+        let limit = self.network.len() * self.directions.len();
+
+        let mut steps = 0;
+        let mut idx = 0;
+        let mut current = start;
+        loop {
+            // Check if we're there
+            if current == end {
+                break;
+            }
+            if steps as usize > limit {
+                return Err(unreachable());
+            }
+            steps += 1;
+            // Update instruction and elem
+            current = self.take(current, self.directions[idx]);
+            log::trace!("step {steps}: {}", self.network.node(current));
+            idx = (idx + 1) % self.directions.len();
+        }
+
+        Ok(steps)
+    }
+
+    /// Where each ghost starting on a node ending in `start` goes round in
+    /// circles, and when it's on a node ending in `end`.
+    fn set_distances(&self, start: &char, end: &char) -> HashMap<String, Cycle> {
+        self.network
+            .nodes()
+            .filter(|(_, k)| k.ends_with(*start))
+            .map(|(id, k)| {
+                let mut hits = Vec::new();
+                // When each (node, direction index) was first reached
+                let mut visited = HashMap::new();
+                let mut current = id;
+                let mut steps: u64 = 0;
+
+                let offset = loop {
+                    let idx = steps as usize % self.directions.len();
+                    if let Some(&seen) = visited.get(&(current, idx)) {
+                        break seen;
+                    }
+                    visited.insert((current, idx), steps);
+                    if self.network.node(current).ends_with(*end) {
+                        hits.push(steps);
+                    }
+
+                    current = self.take(current, self.directions[idx]);
+                    steps += 1;
+                    log::trace!("{k} step {steps}: {}", self.network.node(current));
+                };
+
+                let cycle = Cycle {
+                    offset,
+                    period: steps - offset,
+                    hits,
+                };
+                log::debug!("{k} loops back to step {offset}: {cycle:?}");
+                (k.clone(), cycle)
+            })
+            .collect()
+    }
+
+    /// The network as a Graphviz graph, with each edge labelled by its turn.
+    pub fn to_dot(&self) -> String {
+        self.network.to_dot("day 8", |turn| {
+            Some(match turn {
+                Turn::Left => "L".to_string(),
+                Turn::Right => "R".to_string(),
+            })
+        })
+    }
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |found: &str, expected: &str| ParseError::new(Day8::DAY, s, found, expected);
+
+        let sections = split_sections(s);
+        let (first, network) = match sections.as_slice() {
+            [] => return Err(err(&s[s.len()..], "directions")),
+            [first] => {
+                let line = first.body.first().copied().unwrap_or(&s[s.len()..]);
+                return Err(err(line, "a blank line"));
+            }
+            [first, network] => (first, network),
+            [_, _, extra, ..] => return Err(err(extra.header, "the end of the network")),
+        };
+        if let Some(line) = first.body.first() {
+            return Err(err(line, "a blank line"));
+        }
+
+        let turn = one_of([("L", Turn::Left), ("R", Turn::Right)]);
+        let directions = terminated(many1(turn), label(line_end(), "`L` or `R`"));
+        let directions = parse_within(Day8::DAY, s, first.header, directions)?;
+
+        let next = || {
+            delimited(
+                literal("("),
+                pair(terminated(word(), literal(",")), spaced(word())),
+                literal(")"),
+            )
+        };
+        let nodes = network
+            .lines()
+            .map(|line| parse_within(Day8::DAY, s, line, key_value(word(), "=", next())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut network = Graph::new();
+        for (k, _) in &nodes {
+            if network.id(*k).is_some() {
+                let hint = format!("`{k}` is already defined");
+                return Err(err(k, "a new node").with_hint(hint));
+            }
+            network.intern(k.to_string());
+        }
+        for (k, (l, r)) in nodes {
+            let from = network.id(k).unwrap_or_default();
+            for (next, turn) in [(l, Turn::Left), (r, Turn::Right)] {
+                let Some(to) = network.id(next) else {
+                    let hint = format!("add a `{next} = (<left>, <right>)` line");
+                    return Err(err(next, "a node defined in the network").with_hint(hint));
+                };
+                network.add_edge(from, to, turn);
+            }
+        }
+
+        let mut m = Self {
+            directions,
+            network,
+            distances: HashMap::new(),
+        };
+
+        m.distances = m.set_distances(&'A', &'Z');
+
+        Ok(m)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    input.parse()
+}
+
+pub fn part_1(map: &Map) -> Result<u32, SolveError> {
+    map.steps("AAA", "ZZZ")
+}
+
+pub fn part_2(map: &Map) -> Result<u64, SolveError> {
+    if map.distances.is_empty() {
+        return Err(SolveError::NoSolution("no node ends in `A`".to_string()));
+    }
+
+    let mut cycles = Vec::new();
+    for (start, cycle) in &map.distances {
+        if cycle.hits.is_empty() {
+            return Err(SolveError::NoSolution(format!(
+                "the ghost at `{start}` never reaches a `Z` node"
+            )));
+        }
+        cycles.push(cycle.clone());
+    }
+
+    first_alignment(&cycles)
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const ALT_INPUT: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const INPUT_2: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_parse() {
+        dbg!(INPUT_2.parse::<Map>().unwrap());
+    }
+
+    #[test]
+    fn p1() {
+        assert_eq!(part_1(&parse(INPUT).unwrap()), Ok(2));
+        assert_eq!(part_1(&parse(ALT_INPUT).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn p2() {
+        assert_eq!(part_2(&parse(INPUT_2).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn p2_offset_cycles() {
+        // The ghosts first reach `Z` after 2 and 3 steps but then loop every
+        // 3 and 4, so they meet after 11 rather than lcm(2, 3) = 6
+        let input = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11D, 11D)
+11D = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22D, 22D)
+22D = (22E, 22E)
+22E = (22F, 22F)
+22F = (22Z, 22Z)";
+        assert_eq!(part_2(&parse(input).unwrap()), Ok(11));
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse("RLX\n\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+
+        let err = parse("RL\n\nAAA = (BBB, CCC)\nBBB = (AAA, AAA)").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 13));
+        assert_eq!(err.found, "CCC");
+
+        let err = parse("RL\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a blank line"));
+    }
+
+    #[test]
+    fn line_endings() {
+        let input = ALT_INPUT.replace('\n', "\r\n");
+        assert_eq!(part_1(&parse(&input).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn dot() {
+        let dot = parse(ALT_INPUT).unwrap().to_dot();
+        assert!(dot.starts_with("digraph \"day 8\" {\n    n0 [label=\"AAA\"];"));
+        assert!(dot.contains("    n1 -> n2 [label=\"R\"];"));
+        assert_eq!(dot.matches("->").count(), 6);
+    }
+
+    #[test]
+    fn solve_error() {
+        let map = parse("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert!(matches!(part_1(&map), Err(SolveError::NoSolution(_))));
+        assert!(matches!(part_2(&map), Err(SolveError::NoSolution(_))));
+    }
+}
@@ -0,0 +1,140 @@
+//! Day 9: Mirage Maintenance
+
+use common::{
+    numbers::integers_separated_by,
+    polynomial::{Differences, Polynomial},
+    Answer, ParseError, Solution, SolveError,
+};
+
+fn read_input(s: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    s.lines()
+        .map(|line| {
+            let numbers = integers_separated_by(Day9::DAY, s, line, char::is_whitespace)?;
+            Ok(numbers.into_iter().map(|n| n.value).collect())
+        })
+        .collect()
+}
+
+/// The value after the last one.
+fn repair_history(h: &[i64]) -> Option<i64> {
+    fit(h)?.at(h.len().try_into().ok()?)
+}
+
+/// The value before the first one.
+fn backtrace_history(h: &[i64]) -> Option<i64> {
+    fit(h)?.at(-1)
+}
+
+fn fit(h: &[i64]) -> Option<Polynomial> {
+    let differences = Differences::new(h)?;
+    log::trace!("{h:?}:\n{differences}");
+    Some(differences.polynomial())
+}
+
+/// Extrapolate every history with `f` and add up the results.
+fn sum_histories(histories: &[Vec<i64>], f: fn(&[i64]) -> Option<i64>) -> Result<i64, SolveError> {
+    let mut sum: i64 = 0;
+    for (i, line) in histories.iter().enumerate() {
+        if line.is_empty() {
+            return Err(SolveError::BadInput(format!("history {} is empty", i + 1)));
+        }
+        sum = f(line)
+            .and_then(|n| sum.checked_add(n))
+            .ok_or_else(|| SolveError::Overflow(format!("history {}", i + 1)))?;
+    }
+
+    Ok(sum)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    read_input(input)
+}
+
+pub fn part_1(histories: &[Vec<i64>]) -> Result<i64, SolveError> {
+    sum_histories(histories, repair_history)
+}
+
+pub fn part_2(histories: &[Vec<i64>]) -> Result<i64, SolveError> {
+    sum_histories(histories, backtrace_history)
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Parsed = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Parsed) -> Result<Answer, SolveError> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn check_parse() {
+        dbg!(read_input(INPUT).unwrap());
+    }
+
+    #[test]
+    fn test_differences() {
+        let d = Differences::new(&[0, 3, 6, 9, 12, 15]).unwrap();
+        assert_eq!(d.rows()[1], [3, 3, 3, 3, 3]);
+        assert_eq!(d.rows()[2], [0, 0, 0, 0]);
+        let d = Differences::new(&[1, 3, 6, 10, 15, 21]).unwrap();
+        assert_eq!(
+            d.rows(),
+            [
+                vec![1, 3, 6, 10, 15, 21],
+                vec![2, 3, 4, 5, 6],
+                vec![1, 1, 1, 1],
+                vec![0, 0, 0],
+            ]
+        );
+    }
+
+    #[test]
+    fn extrapolate() {
+        assert_eq!(repair_history(&[10, 13, 16, 21, 30, 45]), Some(68));
+        assert_eq!(backtrace_history(&[10, 13, 16, 21, 30, 45]), Some(5));
+        assert_eq!(repair_history(&[7]), Some(7));
+    }
+
+    #[test]
+    fn p1() {
+        assert_eq!(part_1(&parse(INPUT).unwrap()), Ok(114));
+    }
+
+    #[test]
+    fn p2() {
+        assert_eq!(part_2(&parse(INPUT).unwrap()), Ok(2));
+    }
+
+    #[test]
+    fn solve_error() {
+        assert!(matches!(
+            part_1(&parse("1 2 3\n\n4 5 6").unwrap()),
+            Err(SolveError::BadInput(_))
+        ));
+        let huge = format!("0 {} 0", i64::MAX);
+        assert!(matches!(
+            part_1(&parse(&huge).unwrap()),
+            Err(SolveError::Overflow(_))
+        ));
+    }
+}